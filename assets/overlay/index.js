@@ -1,9 +1,59 @@
-// HTML elements
-const mapName = document.querySelector(".map-name");
-const tpWr = document.querySelector(".tp-wr");
-const proWr = document.querySelector(".pro-wr");
-const tpPb = document.querySelector("#tp-pb");
-const proPb = document.querySelector("#pro-pb");
+// Shared by every layout. Elements are looked up by class, so a layout only needs to contain the
+// ones it actually wants to display.
+//
+// Query parameters:
+//   scale=1.5             scale the whole overlay
+//   align=left|center|right
+//   fields=map,tp,pro,pb  only show elements with a matching `data-field`
+
+// Protocol version this overlay understands
+const PROTOCOL_VERSION = 1;
+
+// Name of this layout, used to look up templates
+const LAYOUT = "__REPLACE_LAYOUT__";
+
+// Element classes rendered from templates on the server; these are left alone otherwise.
+const templated = new Set();
+
+const params = new URLSearchParams(window.location.search);
+
+// Apply layout options
+(() => {
+	const scale = parseFloat(params.get("scale"));
+
+	if (scale > 0) {
+		document.documentElement.style.setProperty("--scale", scale);
+	}
+
+	const align = params.get("align");
+
+	if (["left", "center", "right"].includes(align)) {
+		document.body.dataset.align = align;
+	}
+
+	const fields = params.get("fields");
+
+	if (fields) {
+		const visible = fields.split(",").map((field) => field.trim());
+
+		for (const element of document.querySelectorAll("[data-field]")) {
+			if (!visible.includes(element.dataset.field)) {
+				element.hidden = true;
+			}
+		}
+	}
+})();
+
+// Set the content of every element matching `selector`
+function setText(selector, text) {
+	if (templated.has(selector)) {
+		return;
+	}
+
+	for (const element of document.querySelectorAll(selector)) {
+		element.innerHTML = text;
+	}
+}
 
 // 100.53 => 01:40.530
 function formatTime(seconds) {
@@ -20,141 +70,147 @@ function formatTime(seconds) {
 	return timeString;
 }
 
-// validate map names
-function isKZMap(mapName) {
-	if (!mapName) {
-		return false;
+// kz_timer => KZT
+function formatMode(mode) {
+	switch (mode) {
+		case "kz_timer":
+			return "KZT";
+		case "kz_simple":
+			return "SKZ";
+		case "kz_vanilla":
+			return "VNL";
+		default:
+			return "unknown mode";
 	}
+}
 
-	const prefixes = [
-		"bkz_",
-		"kz_",
-		"kzpro_",
-		"skz_",
-		"vnl_",
-		"xc_",
-	];
+// NEW PB / NEW WR animation
+function showRecord(event) {
+	const runType = event.teleports ? "TP" : "PRO";
+	const title = event.kind === "new_world_record" ? "NEW WR" : "NEW PB";
 
-	for (const prefix of prefixes) {
-		if (mapName.startsWith(prefix)) {
-			return true;
-		}
+	let text = `${title} (${runType}) » ${formatTime(event.new_time)}`;
+
+	if (event.improvement) {
+		text += ` (-${formatTime(event.improvement)})`;
 	}
 
-	return false;
-}
+	for (const recordBanner of document.querySelectorAll(".record-banner")) {
+		recordBanner.innerHTML = text;
+		recordBanner.classList.toggle("wr", event.kind === "new_world_record");
 
-let gameInfo = null;
+		// restart the animation
+		recordBanner.classList.remove("show");
+		void recordBanner.offsetWidth;
+		recordBanner.classList.add("show");
+	}
+}
 
-// Setup WebSocket connection
-const url = new URL("/gsi", window.location.href);
-url.protocol = url.protocol.replace("http", "ws");
+function showState(gameInfo) {
+	const mode = gameInfo?.mode ? formatMode(gameInfo.mode) : null;
+	const tier = gameInfo?.map_tier ? `T${gameInfo.map_tier}` : "not global";
 
-const ws = new WebSocket(url.href);
-ws.onmessage = async (ev) => {
-	console.log("New Info: ", ev?.data);
+	let mapName = `${gameInfo.map_name}`;
 
-	if (!ev) {
-		return;
+	if (mode) {
+		mapName = `[${mode}] ${mapName}`;
 	}
 
-	try {
-		gameInfo = JSON.parse(ev.data);
-	} catch (err) {
-		console.error("Failed to deserialize JSON: ", err);
-	}
+	mapName += ` (${tier})`;
 
-	if (!gameInfo) {
-		return;
-	}
+	setText(".map-name", mapName);
+	setText(".map", `${gameInfo.map_name}`);
+	setText(".mode", mode ?? "");
+	setText(".tier", tier);
+}
 
-	mapName.innerHTML = `${gameInfo.map_name}`;
+function showRecords(runType, wr, pb) {
+	if (wr) {
+		setText(`.${runType}-wr`, `${formatTime(wr.time)} by ${wr.player_name}`);
 
-	if (gameInfo?.mode) {
-		let mode;
-		switch (gameInfo.mode) {
-			case "kz_timer": {
-				mode = "KZT";
-				break;
-			}
-			case "kz_simple": {
-				mode = "SKZ";
-				break;
-			}
-			case "kz_vanilla": {
-				mode = "VNL";
-				break;
-			}
-			default: {
-				mode = "unknown mode";
-			}
+		if (pb && pb.time - wr.time != 0) {
+			setText(`.${runType}-pb`, `(+${formatTime(pb.time - wr.time)})`);
+		} else {
+			setText(`.${runType}-pb`, "");
 		}
-		mapName.innerHTML = `[${mode}] ${mapName.innerHTML}`;
-	}
 
-	if (gameInfo?.map_tier) {
-		mapName.innerHTML += ` (T${gameInfo.map_tier})`;
 	} else {
-		mapName.innerHTML += " (not global)";
+		setText(`.${runType}-wr`, "no WR");
+		setText(`.${runType}-pb`, "");
 	}
-};
+}
 
-setInterval(async () => {
-	if (!gameInfo) {
-		return;
-	}
+// Stream session stats, for themes that want to show them
+function showSession(session) {
+	const minutes = Math.floor((session.time_on_current_map ?? 0) / 60);
 
-	const shouldFetchRecords = gameInfo.player_name
-		&& gameInfo.mode
-		&& isKZMap(gameInfo.map_name);
+	setText(".maps-played", `${session.maps_played}`);
+	setText(".time-on-map", session.current_map ? `${minutes}m` : "");
+	setText(".session-pbs", `${session.personal_bests}`);
+	setText(".session-wrs", `${session.world_records}`);
+}
 
-	const [tp_wr, pro_wr] = shouldFetchRecords
-		? await fetch(
-			`http://localhost:9999/wrs?steam_id=${gameInfo.steam_id}&map_identifier=${gameInfo.map_name}&mode=${gameInfo.mode}`
-		)
-			.then((res) => res.json())
-			.catch(console.error)
-		: [null, null];
+// Text rendered from the templates in the config
+function showText(texts) {
+	templated.clear();
 
-	console.log("TP WR: ", tp_wr);
-	console.log("PRO WR: ", tp_wr);
+	for (const [className, text] of Object.entries(texts[LAYOUT] ?? {})) {
+		setText(`.${className}`, text);
+		templated.add(`.${className}`);
+	}
+}
 
-	const [tp_pb, pro_pb] = shouldFetchRecords
-		? await fetch(
-			`http://localhost:9999/pbs?steam_id=${gameInfo.steam_id}&map_identifier=${gameInfo.map_name}&mode=${gameInfo.mode}`
-		)
-			.then((res) => res.json())
-			.catch(console.error)
-		: [null, null];
+// Setup WebSocket connection
+const url = new URL("/gsi", window.location.href);
+url.protocol = url.protocol.replace("http", "ws");
 
-	console.log("TP PB: ", tp_wr);
-	console.log("PRO PB: ", tp_wr);
+const ws = new WebSocket(url.href);
 
-	if (tp_wr) {
-		tpWr.innerHTML = `${formatTime(tp_wr.time)} by ${tp_wr.player_name}`;
+ws.onopen = () => {
+	ws.send(JSON.stringify({ type: "subscribe", types: ["state", "records", "event", "text", "session"] }));
+};
 
-		if (tp_pb && tp_pb.time - tp_wr.time != 0) {
-			tpPb.innerHTML = `(+${formatTime(tp_pb.time - tp_wr.time)})`;
-		} else {
-			tpPb.innerHTML = "";
-		}
+ws.onmessage = async (ev) => {
+	console.log("New Message: ", ev?.data);
 
-	} else {
-		tpWr.innerHTML = "no WR";
-		tpPb.innerHTML = "";
+	if (!ev) {
+		return;
 	}
 
-	if (pro_wr) {
-		proWr.innerHTML = `${formatTime(pro_wr.time)} by ${pro_wr.player_name}`;
+	let message = null;
 
-		if (pro_pb && pro_pb.time - pro_wr.time != 0) {
-			proPb.innerHTML = `(+${formatTime(pro_pb.time - pro_wr.time)})`;
-		} else {
-			proPb.innerHTML = "";
-		}
+	try {
+		message = JSON.parse(ev.data);
+	} catch (err) {
+		return console.error("Failed to deserialize JSON: ", err);
+	}
 
-	} else {
-		proWr.innerHTML = "no WR";
-		proPb.innerHTML = "";
+	if (message?.v !== PROTOCOL_VERSION) {
+		return console.error("Unsupported protocol version: ", message?.v);
 	}
-}, 3000);
+
+	switch (message.type) {
+		case "state": {
+			showState(message.data);
+			break;
+		}
+		case "records": {
+			const { tp_wr, pro_wr, tp_pb, pro_pb } = message.data;
+			showRecords("tp", tp_wr, tp_pb);
+			showRecords("pro", pro_wr, pro_pb);
+			break;
+		}
+		case "event": {
+			showRecord(message.data);
+			break;
+		}
+		case "text": {
+			showText(message.data);
+			break;
+		}
+		case "session": {
+			showSession(message.data);
+			break;
+		}
+	}
+};
@@ -0,0 +1,54 @@
+// Injected into overlays while live-reload is enabled.
+//
+// Stylesheets are swapped in place, any other change reloads the whole page.
+(() => {
+	const url = new URL("/dev/reload", window.location.href);
+	url.protocol = url.protocol.replace("http", "ws");
+
+	function reloadCss(path) {
+		const links = [...document.querySelectorAll('link[rel="stylesheet"]')]
+			.filter((link) => new URL(link.href).pathname.endsWith(`/${path}`));
+
+		// Inline or unknown stylesheet; nothing to swap.
+		if (links.length === 0) {
+			return window.location.reload();
+		}
+
+		for (const link of links) {
+			const href = new URL(link.href);
+			href.searchParams.set("reload", Date.now());
+			link.href = href.href;
+		}
+	}
+
+	function connect() {
+		const ws = new WebSocket(url.href);
+
+		ws.onmessage = (ev) => {
+			console.log("Live-reload: ", ev?.data);
+
+			let reload = null;
+
+			try {
+				reload = JSON.parse(ev.data);
+			} catch (err) {
+				return console.error("Failed to deserialize JSON: ", err);
+			}
+
+			switch (reload.type) {
+				case "css": {
+					reloadCss(reload.path);
+					break;
+				}
+				default: {
+					window.location.reload();
+				}
+			}
+		};
+
+		// The client might get restarted while editing.
+		ws.onclose = () => setTimeout(connect, 1000);
+	}
+
+	connect();
+})();
@@ -0,0 +1,48 @@
+//! Files embedded into the binary, so the GUI and overlays work without an internet connection.
+
+use std::path::Path;
+
+pub const QUICKSAND: &[u8] = include_bytes!("../assets/fonts/quicksand.ttf");
+pub const FIRA_CODE: &[u8] = include_bytes!("../assets/fonts/firacode.ttf");
+
+/// Everything served under `/assets/`.
+const FILES: [(&str, &[u8]); 4] = [
+	("fonts/fonts.css", include_bytes!("../assets/fonts/fonts.css")),
+	("fonts/quicksand.ttf", QUICKSAND),
+	("fonts/firacode.ttf", FIRA_CODE),
+	("icons/favicon.svg", include_bytes!("../assets/icons/favicon.svg")),
+];
+
+/// Looks up an embedded file by its path relative to `/assets/`.
+pub fn get(path: &str) -> Option<&'static [u8]> {
+	FILES
+		.into_iter()
+		.find_map(|(name, contents)| (name == path).then_some(contents))
+}
+
+/// Best guess at a file's MIME type based on its extension.
+pub fn mime_type(path: &Path) -> &'static str {
+	match path
+		.extension()
+		.and_then(|extension| extension.to_str())
+		.map(|extension| extension.to_ascii_lowercase())
+		.as_deref()
+	{
+		Some("html" | "htm") => "text/html; charset=utf-8",
+		Some("css") => "text/css; charset=utf-8",
+		Some("js" | "mjs") => "text/javascript; charset=utf-8",
+		Some("json") => "application/json",
+		Some("txt") => "text/plain; charset=utf-8",
+		Some("svg") => "image/svg+xml",
+		Some("png") => "image/png",
+		Some("jpg" | "jpeg") => "image/jpeg",
+		Some("gif") => "image/gif",
+		Some("webp") => "image/webp",
+		Some("ico") => "image/x-icon",
+		Some("ttf") => "font/ttf",
+		Some("otf") => "font/otf",
+		Some("woff") => "font/woff",
+		Some("woff2") => "font/woff2",
+		_ => "application/octet-stream",
+	}
+}
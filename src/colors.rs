@@ -32,3 +32,40 @@ pub const CRUST: Color32 = Color32::from_rgb(17, 17, 27);
 
 /// not catppuccin, but the best color in existence
 pub const POGGERS: Color32 = Color32::from_rgb(116, 128, 194);
+
+/// Every color above, by name.
+pub const PALETTE: [(&str, Color32); 27] = [
+	("Rosewater", ROSEWATER),
+	("Flamingo", FLAMINGO),
+	("Pink", PINK),
+	("Mauve", MAUVE),
+	("Red", RED),
+	("Maroon", MAROON),
+	("Peach", PEACH),
+	("Yellow", YELLOW),
+	("Green", GREEN),
+	("Teal", TEAL),
+	("Sky", SKY),
+	("Sapphire", SAPPHIRE),
+	("Blue", BLUE),
+	("Lavender", LAVENDER),
+	("Text", TEXT),
+	("Subtext1", SUBTEXT1),
+	("Subtext0", SUBTEXT0),
+	("Overlay2", OVERLAY2),
+	("Overlay1", OVERLAY1),
+	("Overlay0", OVERLAY0),
+	("Surface2", SURFACE2),
+	("Surface1", SURFACE1),
+	("Surface0", SURFACE0),
+	("Base", BASE),
+	("Mantle", MANTLE),
+	("Crust", CRUST),
+	("Poggers", POGGERS),
+];
+
+pub fn by_name(name: &str) -> Option<Color32> {
+	PALETTE
+		.into_iter()
+		.find_map(|(color_name, color)| (color_name == name).then_some(color))
+}
@@ -1,10 +1,11 @@
 use {
+	crate::gsi::Game,
 	color_eyre::{
 		eyre::{bail as yeet, Context},
 		Result,
 	},
 	serde::{Deserialize, Deserializer, Serialize, Serializer},
-	std::{path::PathBuf, str::FromStr},
+	std::{collections::BTreeMap, path::PathBuf, str::FromStr},
 	uuid::Uuid,
 };
 
@@ -13,11 +14,222 @@ pub struct Config {
 	#[serde(serialize_with = "ser_none_as_empty")]
 	#[serde(deserialize_with = "deser_empty_as_none")]
 	pub csgo_cfg_path: Option<PathBuf>,
+	/// CS2's `game/csgo/cfg` folder.
+	#[serde(default)]
+	#[serde(serialize_with = "ser_none_as_empty")]
+	#[serde(deserialize_with = "deser_empty_as_none")]
+	pub cs2_cfg_path: Option<PathBuf>,
+	/// Which game to install the GSI cfg into and parse events for.
+	#[serde(default)]
+	pub game: Game,
 	pub gsi_port: u16,
 	pub api_url: String,
 	#[serde(serialize_with = "ser_none_as_empty")]
 	#[serde(deserialize_with = "deser_empty_as_none")]
 	pub schnose_api_key: Option<Uuid>,
+	/// URLs that get a POST request for every new PB / WR.
+	#[serde(default)]
+	pub webhooks: Vec<String>,
+	/// Name of a folder in [`Config::themes_dir`]. Uses the built-in overlay if empty.
+	#[serde(default)]
+	#[serde(serialize_with = "ser_none_as_empty")]
+	#[serde(deserialize_with = "deser_empty_as_none")]
+	pub overlay_theme: Option<String>,
+	/// Reload overlays whenever their files change.
+	#[serde(default)]
+	pub overlay_live_reload: bool,
+	/// Overlay name -> element class -> template, e.g.
+	/// `overlay_templates.compact.map-name = "{mode_short} · {map} · Tier {tier}"`.
+	///
+	/// See [`crate::template`] for the syntax.
+	#[serde(default)]
+	pub overlay_templates: BTreeMap<String, BTreeMap<String, String>>,
+	/// Settings of the overlay editor tab.
+	#[serde(default)]
+	pub overlay_style: OverlayStyle,
+	/// Folder to write [`Config::text_files`] into, for OBS text sources. Disabled if empty.
+	#[serde(default)]
+	#[serde(serialize_with = "ser_none_as_empty")]
+	#[serde(deserialize_with = "deser_empty_as_none")]
+	pub text_files_dir: Option<PathBuf>,
+	/// File name -> template, e.g. `text_files."map.txt" = "{map}"`.
+	///
+	/// See [`crate::template`] for the syntax.
+	#[serde(default = "default_text_files")]
+	pub text_files: BTreeMap<String, String>,
+	#[serde(default)]
+	pub history: HistoryConfig,
+	/// `RUST_LOG`-style directives, e.g. `info` or `info,schnose_gsi_client::gsi=trace`.
+	#[serde(default = "default_log_level")]
+	pub log_level: String,
+	/// How many log lines the Logs tab keeps around.
+	#[serde(default = "default_log_capacity")]
+	pub log_capacity: usize,
+	#[serde(default)]
+	pub log_files: LogFilesConfig,
+}
+
+fn default_log_level() -> String {
+	String::from("info")
+}
+
+const fn default_log_capacity() -> usize {
+	crate::logger::DEFAULT_CAPACITY
+}
+
+/// Settings for [`crate::history`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryConfig {
+	/// Record state transitions and records into `history.jsonl`.
+	pub enabled: bool,
+	/// Entries older than this many days get deleted on startup. `0` keeps everything.
+	pub retention_days: u32,
+}
+
+impl Default for HistoryConfig {
+	fn default() -> Self {
+		Self { enabled: true, retention_days: 90 }
+	}
+}
+
+/// Settings for [`crate::log_files`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogFilesConfig {
+	/// Also write logs into the `logs` folder next to the config file.
+	pub enabled: bool,
+	pub format: LogFormat,
+	pub rotation: Rotation,
+	/// Only used for [`Rotation::Size`].
+	pub max_size_mb: u64,
+	/// How many log files to keep. Older ones get deleted.
+	pub keep: usize,
+}
+
+impl Default for LogFilesConfig {
+	fn default() -> Self {
+		Self {
+			enabled: true,
+			format: LogFormat::Plain,
+			rotation: Rotation::Daily,
+			max_size_mb: 10,
+			keep: 7,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+	Plain,
+	/// One JSON object per line, same as the Logs tab.
+	Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rotation {
+	/// Start a new file every day.
+	Daily,
+	/// Start a new file once the current one reaches `max_size_mb`.
+	Size,
+}
+
+fn default_text_files() -> BTreeMap<String, String> {
+	[
+		("player.txt", "{player}"),
+		("map.txt", "{map}"),
+		("tier.txt", "{tier}"),
+		("mode.txt", "{mode_short}"),
+		("wr_tp.txt", "{tp_wr_time} by {tp_wr_player}"),
+		("wr_pro.txt", "{pro_wr_time} by {pro_wr_player}"),
+		("pb_tp.txt", "{tp_pb_time}"),
+		("pb_pro.txt", "{pro_pb_time}"),
+	]
+	.into_iter()
+	.map(|(file_name, template)| (String::from(file_name), String::from(template)))
+	.collect()
+}
+
+/// An overlay put together in the editor tab. Based on the `compact` layout.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OverlayStyle {
+	/// Name of the theme this gets saved as.
+	pub name: String,
+	pub show_map: bool,
+	pub show_tp: bool,
+	pub show_pro: bool,
+	pub show_pb: bool,
+	pub show_events: bool,
+	/// Names of colors in [`crate::colors::PALETTE`].
+	pub text_color: String,
+	pub tp_color: String,
+	pub pro_color: String,
+	pub pb_color: String,
+	/// Either `Quicksand` or `Fira Code`.
+	pub font: String,
+	/// Font sizes in `em`.
+	pub map_size: f32,
+	pub records_size: f32,
+	pub map_template: String,
+	pub tp_template: String,
+	pub pro_template: String,
+}
+
+impl Default for OverlayStyle {
+	fn default() -> Self {
+		Self {
+			name: String::from("custom"),
+			show_map: true,
+			show_tp: true,
+			show_pro: true,
+			show_pb: true,
+			show_events: true,
+			text_color: String::from("Text"),
+			tp_color: String::from("Yellow"),
+			pro_color: String::from("Sapphire"),
+			pb_color: String::from("Red"),
+			font: String::from("Quicksand"),
+			map_size: 3.5,
+			records_size: 2.5,
+			map_template: String::from("[{mode_short}] {map} (T{tier})"),
+			tp_template: String::from("{tp_wr_time} by {tp_wr_player}"),
+			pro_template: String::from("{pro_wr_time} by {pro_wr_player}"),
+		}
+	}
+}
+
+impl OverlayStyle {
+	pub const FONTS: [&str; 2] = ["Quicksand", "Fira Code"];
+
+	/// `data-field`s that should be visible.
+	pub fn fields(&self) -> Vec<&'static str> {
+		[
+			(self.show_map, "map"),
+			(self.show_tp, "tp"),
+			(self.show_pro, "pro"),
+			(self.show_pb, "pb"),
+			(self.show_events, "events"),
+		]
+		.into_iter()
+		.filter_map(|(show, field)| show.then_some(field))
+		.collect()
+	}
+
+	/// Templates in the format of [`Config::overlay_templates`].
+	pub fn templates(&self) -> BTreeMap<String, String> {
+		[
+			("map-name", &self.map_template),
+			("tp-wr", &self.tp_template),
+			("pro-wr", &self.pro_template),
+		]
+		.into_iter()
+		.filter(|(_, template)| !template.is_empty())
+		.map(|(class, template)| (String::from(class), template.clone()))
+		.collect()
+	}
 }
 
 impl Config {
@@ -58,6 +270,10 @@ impl Config {
 				gsi_port = 8888
 				api_url = 'https://schnose-twitch-bot.shuttleapp.rs/streamer'
 				schnose_api_key = ''
+				webhooks = []
+				overlay_theme = ''
+				overlay_live_reload = false
+				text_files_dir = ''
 			"#
 			.trim_start()
 			.replace('\t', "");
@@ -73,6 +289,57 @@ impl Config {
 		Ok(config_dir)
 	}
 
+	/// The cfg folder of the selected [`Config::game`].
+	pub fn cfg_path(&self) -> Option<&PathBuf> {
+		match self.game {
+			Game::Csgo => self.csgo_cfg_path.as_ref(),
+			Game::Cs2 => self.cs2_cfg_path.as_ref(),
+		}
+	}
+
+	pub fn cfg_path_mut(&mut self) -> &mut Option<PathBuf> {
+		match self.game {
+			Game::Csgo => &mut self.csgo_cfg_path,
+			Game::Cs2 => &mut self.cs2_cfg_path,
+		}
+	}
+
+	/// The folder containing custom overlay themes, next to the config file. It's created on
+	/// startup, see [`Config::create_themes_dir`].
+	#[tracing::instrument]
+	pub fn themes_dir() -> Result<PathBuf> {
+		let mut themes_dir = Self::find_path()?;
+		themes_dir.pop();
+		themes_dir.push("themes");
+
+		Ok(themes_dir)
+	}
+
+	/// Creates [`Config::themes_dir`] if it doesn't exist yet.
+	pub fn create_themes_dir() -> Result<()> {
+		std::fs::create_dir_all(Self::themes_dir()?).context("Failed to create themes folder.")
+	}
+
+	/// The folder containing log files, next to the config file.
+	pub fn logs_dir() -> Result<PathBuf> {
+		let mut logs_dir = Self::find_path()?;
+		logs_dir.pop();
+		logs_dir.push("logs");
+
+		if !logs_dir.exists() {
+			std::fs::create_dir(&logs_dir).context("Failed to create logs folder.")?;
+		}
+
+		Ok(logs_dir)
+	}
+
+	/// The history database, next to the config file.
+	pub fn history_path() -> Result<PathBuf> {
+		let mut history_path = Self::find_path()?;
+		history_path.set_file_name("history.jsonl");
+		Ok(history_path)
+	}
+
 	#[tracing::instrument]
 	pub fn load() -> Result<Self> {
 		let config_dir = Self::find_path()?;
@@ -0,0 +1,198 @@
+//! Collects everything needed to debug a broken setup into a single zip file, so users can attach
+//! it to a support request instead of answering questions one by one.
+//!
+//! Everything that ends up in the zip goes through [`Redactor`] first.
+
+use {
+	crate::{
+		config::Config,
+		gsi,
+		history::{EntryKind, Filter, History},
+		redact::Redactor,
+		server,
+	},
+	chrono::Local,
+	color_eyre::{eyre::Context, Result},
+	std::{
+		fmt::Write as _,
+		fs::File,
+		io::Write,
+		net::TcpListener,
+		path::{Path, PathBuf},
+	},
+	tracing::info,
+	zip::{write::FileOptions, CompressionMethod, ZipWriter},
+};
+
+/// How many of the newest log files get included.
+const LOG_FILES: usize = 3;
+
+/// How many of the newest history entries get included.
+const HISTORY_ENTRIES: usize = 50;
+
+/// `schnose-gsi-client-diagnostics-20230401133700.zip`
+pub fn default_file_name() -> String {
+	format!(
+		"schnose-gsi-client-diagnostics-{}.zip",
+		Local::now().format("%Y%m%d%H%M%S")
+	)
+}
+
+/// Writes the bundle to `path`.
+///
+/// `logs` are the logs collected by the GUI, if there are any.
+pub async fn create(config: &Config, logs: Option<String>, path: &Path) -> Result<()> {
+	let redactor = Redactor::new(config);
+
+	let mut files = vec![
+		(String::from("summary.txt"), summary(config)),
+		(String::from("config.toml"), config_toml(config)?),
+	];
+
+	if let Some(logs) = logs {
+		files.push((String::from("logs/gui.jsonl"), logs));
+	}
+
+	files.extend(log_files());
+	files.extend(gsi_configs(config));
+
+	let events = gsi::recent_events()
+		.into_iter()
+		.map(|(at, event)| format!("# {}\n{event}\n\n", at.with_timezone(&Local)))
+		.collect::<String>();
+
+	if !events.is_empty() {
+		files.push((String::from("gsi_events.txt"), events));
+	}
+
+	if let Ok(history) = History::open() {
+		let entries = history.query(&Filter::default()).await.unwrap_or_default();
+		let start = entries.len().saturating_sub(HISTORY_ENTRIES);
+
+		let states = entries[start..]
+			.iter()
+			.filter(|entry| matches!(entry.kind, EntryKind::State(_)))
+			.filter_map(|entry| serde_json::to_string(entry).ok())
+			.map(|line| line + "\n")
+			.collect::<String>();
+
+		if !states.is_empty() {
+			files.push((String::from("history.jsonl"), states));
+		}
+	}
+
+	let mut zip = ZipWriter::new(File::create(path).context("Failed to create zip file.")?);
+	let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+	for (name, contents) in files {
+		zip.start_file(&name, options)
+			.with_context(|| format!("Failed to add `{name}` to zip file."))?;
+
+		zip.write_all(redactor.redact(&contents).as_bytes())
+			.with_context(|| format!("Failed to write `{name}` to zip file."))?;
+	}
+
+	zip.finish().context("Failed to finish zip file.")?;
+
+	info!("Wrote diagnostics to `{}`.", path.display());
+
+	Ok(())
+}
+
+fn summary(config: &Config) -> String {
+	let mut summary = String::new();
+
+	let _ = writeln!(summary, "schnose-gsi-client v{}", env!("CARGO_PKG_VERSION"));
+	let _ = writeln!(summary, "Created: {}", Local::now().to_rfc3339());
+	let _ = writeln!(summary, "OS: {}", os_info::get());
+	let _ = writeln!(
+		summary,
+		"Target: {} {}",
+		std::env::consts::OS,
+		std::env::consts::ARCH
+	);
+
+	let _ = writeln!(summary, "\n# Paths");
+	let _ = writeln!(summary, "Config: {}", display(Config::find_path().ok()));
+	let _ = writeln!(summary, "Game: {}", config.game.name());
+	let _ = writeln!(summary, "cfg: {}", display(config.cfg_path().cloned()));
+	let _ = writeln!(summary, "Logs: {}", display(Config::logs_dir().ok()));
+
+	let _ = writeln!(summary, "\n# Ports");
+	let _ = writeln!(summary, "GSI ({}): {}", config.gsi_port, port_status(config.gsi_port));
+	let _ = writeln!(summary, "Overlay ({}): {}", server::PORT, port_status(server::PORT));
+	let _ = writeln!(summary, "(Ports are expected to be in use while the client is running.)");
+
+	summary
+}
+
+fn display(path: Option<PathBuf>) -> String {
+	match path {
+		Some(path) if !path.as_os_str().is_empty() => path.display().to_string(),
+		_ => String::from("(not set)"),
+	}
+}
+
+/// Fails if something else is already listening on `port`.
+pub fn check_port(port: u16) -> std::io::Result<()> {
+	TcpListener::bind(("127.0.0.1", port)).map(drop)
+}
+
+fn port_status(port: u16) -> String {
+	match check_port(port) {
+		Ok(()) => String::from("free"),
+		Err(why) => format!("in use ({why})"),
+	}
+}
+
+fn config_toml(config: &Config) -> Result<String> {
+	toml::to_string_pretty(config).context("Failed to serialize config.")
+}
+
+/// The newest [`LOG_FILES`] files from [`Config::logs_dir`].
+fn log_files() -> Vec<(String, String)> {
+	let Ok(entries) = Config::logs_dir().and_then(|dir| Ok(std::fs::read_dir(dir)?)) else {
+		return Vec::new();
+	};
+
+	let mut paths = entries
+		.filter_map(|entry| entry.ok())
+		.map(|entry| entry.path())
+		.filter(|path| path.is_file())
+		.collect::<Vec<_>>();
+
+	// File names start with a timestamp.
+	paths.sort();
+
+	paths
+		.into_iter()
+		.rev()
+		.take(LOG_FILES)
+		.filter_map(|path| {
+			let name = path.file_name()?.to_str()?.to_owned();
+			let contents = std::fs::read_to_string(&path).ok()?;
+			Some((format!("logs/{name}"), contents))
+		})
+		.collect()
+}
+
+/// Every `gamestate_integration_*.cfg` in the configured cfg folder.
+fn gsi_configs(config: &Config) -> Vec<(String, String)> {
+	let Some(Ok(entries)) = config.cfg_path().map(std::fs::read_dir) else {
+		return Vec::new();
+	};
+
+	entries
+		.filter_map(|entry| entry.ok())
+		.filter_map(|entry| {
+			let name = entry.file_name().to_str()?.to_owned();
+
+			if !name.starts_with("gamestate_integration_") || !name.ends_with(".cfg") {
+				return None;
+			}
+
+			let contents = std::fs::read_to_string(entry.path()).ok()?;
+			Some((format!("cfg/{name}"), contents))
+		})
+		.collect()
+}
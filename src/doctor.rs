@@ -0,0 +1,266 @@
+//! Checks everything the client needs to work and explains how to fix whatever is broken.
+
+use {
+	crate::{
+		config::Config,
+		diagnose::check_port,
+		gsi::{
+			cfg::{self, Difference},
+			Game,
+		},
+		server,
+		status::{Tracker, TwitchBotStatus},
+	},
+	gokz_rs::{global_api, MapIdentifier},
+	std::time::Duration,
+	tokio::sync::Mutex,
+};
+
+/// How long the game gets to send its first event before that check fails.
+pub const FIRST_EVENT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The name of the [`first_event`] check.
+pub const FIRST_EVENT: &str = "First GSI event";
+
+#[derive(Debug, Clone)]
+pub struct Check {
+	pub name: &'static str,
+	pub outcome: Outcome,
+	pub details: String,
+	/// What the user can do about it, if the check didn't pass.
+	pub fix: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+	Passed,
+	Warning,
+	Failed,
+	/// Can't be decided yet, e.g. because the server isn't running.
+	Pending,
+}
+
+impl Check {
+	fn passed(name: &'static str, details: impl Into<String>) -> Self {
+		Self { name, outcome: Outcome::Passed, details: details.into(), fix: None }
+	}
+
+	fn pending(name: &'static str, details: impl Into<String>) -> Self {
+		Self { name, outcome: Outcome::Pending, details: details.into(), fix: None }
+	}
+
+	fn warning(name: &'static str, details: impl Into<String>, fix: impl Into<String>) -> Self {
+		Self {
+			name,
+			outcome: Outcome::Warning,
+			details: details.into(),
+			fix: Some(fix.into()),
+		}
+	}
+
+	fn failed(name: &'static str, details: impl Into<String>, fix: impl Into<String>) -> Self {
+		Self {
+			name,
+			outcome: Outcome::Failed,
+			details: details.into(),
+			fix: Some(fix.into()),
+		}
+	}
+}
+
+/// Runs every check. `tracker` is only available while the server is running.
+pub async fn run(config: &Config, tracker: Option<&Mutex<Tracker>>) -> Vec<Check> {
+	let running = tracker.is_some();
+	let gokz_client = gokz_rs::Client::new();
+
+	let mut checks = vec![cfg_folder(config), gsi_cfg(config)];
+
+	checks.push(port(
+		"GSI port",
+		config.gsi_port,
+		running,
+		"Close whatever is using the port (maybe another instance of the client), or pick a \
+		 different `gsi_port` in the config.",
+	));
+
+	checks.push(port(
+		"Overlay port",
+		server::PORT,
+		running,
+		format!(
+			"Close whatever is using the port, maybe another instance of the client. The overlay \
+			 always runs on port {}, so it can't be changed.",
+			server::PORT
+		),
+	));
+
+	checks.push(global_api(&gokz_client).await);
+
+	match tracker {
+		None => {
+			checks.push(api_key(config, None));
+			checks.push(first_event(config.game, None));
+		}
+		Some(tracker) => {
+			let tracker = tracker.lock().await;
+			checks.push(api_key(config, Some(&tracker)));
+			checks.push(first_event(config.game, Some(&tracker)));
+		}
+	}
+
+	checks
+}
+
+fn cfg_fix(game: Game) -> String {
+	format!(
+		"Select the `{}` folder inside your {} install, usually `{}`.",
+		game.cfg_folder(),
+		game.name(),
+		game.cfg_path_in_library().join("/"),
+	)
+}
+
+fn cfg_folder(config: &Config) -> Check {
+	const NAME: &str = "cfg folder";
+
+	let game = config.game;
+
+	let path = match config.cfg_path() {
+		Some(path) if !path.as_os_str().is_empty() => path,
+		_ => return Check::failed(NAME, "No cfg folder selected.", cfg_fix(game)),
+	};
+
+	if !path.is_dir() {
+		let details = format!("`{}` doesn't exist.", path.display());
+		return Check::failed(NAME, details, cfg_fix(game));
+	}
+
+	if !game.looks_like_cfg_folder(path) {
+		return Check::warning(
+			NAME,
+			format!("`{}` doesn't look like {}'s cfg folder.", path.display(), game.name()),
+			cfg_fix(game),
+		);
+	}
+
+	Check::passed(NAME, path.display().to_string())
+}
+
+fn gsi_cfg(config: &Config) -> Check {
+	const NAME: &str = "GSI cfg";
+
+	let game = config.game.name();
+
+	let Some(cfg_dir) = config.cfg_path() else {
+		return Check::pending(NAME, "Select a cfg folder first.");
+	};
+
+	match cfg::diff(cfg_dir, config.gsi_port) {
+		Ok(differences) if differences.is_empty() => {
+			Check::passed(NAME, cfg::path(cfg_dir).display().to_string())
+		}
+		Ok(differences) if differences == [Difference::Missing] => Check::failed(
+			NAME,
+			format!("`{}` is missing.", cfg::path(cfg_dir).display()),
+			format!("Start the server once or click \"Repair GSI cfg\", then restart {game}."),
+		),
+		Ok(differences) => Check::failed(
+			NAME,
+			format!("The installed cfg is outdated: {}.", cfg::describe(&differences)),
+			format!("Click \"Repair GSI cfg\", then restart {game}."),
+		),
+		Err(why) => Check::failed(
+			NAME,
+			format!("{why}"),
+			"Make sure the client is allowed to read and write the cfg folder.",
+		),
+	}
+}
+
+fn port(name: &'static str, port: u16, running: bool, fix: impl Into<String>) -> Check {
+	if running {
+		return Check::passed(name, format!("{port} is used by the client."));
+	}
+
+	match check_port(port) {
+		Ok(()) => Check::passed(name, format!("{port} is free.")),
+		Err(why) => Check::failed(name, format!("{port} is already in use: {why}"), fix),
+	}
+}
+
+async fn global_api(gokz_client: &gokz_rs::Client) -> Check {
+	const NAME: &str = "GlobalAPI";
+
+	let map = MapIdentifier::Name(String::from("kz_grotto"));
+
+	match global_api::get_map(&map, gokz_client).await {
+		Ok(_) => Check::passed(NAME, "Reachable."),
+		Err(why) => Check::failed(
+			NAME,
+			format!("Not reachable: {why}"),
+			"Check your internet connection. If it works, the GlobalAPI might be down; map \
+			 information and records won't show up until it's back.",
+		),
+	}
+}
+
+fn api_key(config: &Config, tracker: Option<&Tracker>) -> Check {
+	const NAME: &str = "API key";
+
+	if config.schnose_api_key.is_none() {
+		return Check::warning(
+			NAME,
+			"No API key entered, the Twitch Bot won't be notified.",
+			"Enter the API key you got from the Twitch Bot, if you use it.",
+		);
+	}
+
+	let Some(tracker) = tracker else {
+		return Check::pending(NAME, "Start the server and load a map to check this.");
+	};
+
+	let status = tracker.status();
+
+	match status.twitch_bot {
+		TwitchBotStatus::Healthy => Check::passed(NAME, "Accepted by the Twitch Bot."),
+		TwitchBotStatus::Failing => Check::failed(
+			NAME,
+			status
+				.twitch_bot_error
+				.unwrap_or_else(|| String::from("The Twitch Bot rejected the last request.")),
+			"Make sure you copied the whole API key and that `api_url` in the config is correct.",
+		),
+		TwitchBotStatus::Waiting | TwitchBotStatus::Disabled => {
+			Check::pending(NAME, "Load a map to check this.")
+		}
+	}
+}
+
+/// Cheap enough to repeat while it's pending. `tracker` is only available while the server is
+/// running.
+pub fn first_event(game: Game, tracker: Option<&Tracker>) -> Check {
+	const NAME: &str = FIRST_EVENT;
+
+	let Some(tracker) = tracker else {
+		return Check::pending(NAME, "Start the server to check this.");
+	};
+
+	let game = game.name();
+
+	if let Some(age) = tracker.status().last_event_age {
+		return Check::passed(NAME, format!("Last event {age:.0}s ago."));
+	}
+
+	match tracker.listening_for() {
+		Some(waited) if waited > FIRST_EVENT_TIMEOUT => Check::failed(
+			NAME,
+			format!("{game} hasn't sent anything in {}s.", waited.as_secs()),
+			format!(
+				"Restart {game}, it only reads GSI cfgs on startup. If that doesn't help, make \
+				 sure the GSI cfg check passes."
+			),
+		),
+		Some(_) => Check::pending(NAME, format!("Waiting for {game}...")),
+		None => Check::pending(NAME, "The GSI server isn't listening yet."),
+	}
+}
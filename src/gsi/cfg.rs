@@ -0,0 +1,328 @@
+//! The `gamestate_integration_*.cfg` file that tells the game where to send events.
+//!
+//! The game only reads it on startup, so every change needs a restart to take effect.
+
+use {
+	crate::vdf,
+	color_eyre::{eyre::Context, Result},
+	schnose_gsi::{GSIConfigBuilder, GSIServer, Subscription},
+	std::{
+		fmt,
+		path::{Path, PathBuf},
+		time::Duration,
+	},
+	tracing::info,
+};
+
+/// The name the game knows the client by.
+pub const NAME: &str = "schnose-gsi-client";
+
+pub const FILE_NAME: &str = "gamestate_integration_schnose-gsi-client.cfg";
+
+pub const HEARTBEAT: Duration = Duration::from_secs(1);
+
+/// What the client subscribes to, and what those are called inside the cfg.
+const SUBSCRIPTIONS: [(Subscription, &str); 2] = [
+	(Subscription::Map, "map"),
+	(Subscription::PlayerID, "player_id"),
+];
+
+/// A GSI server that installs and listens for the cfg described in this module.
+pub fn server(port: u16) -> GSIServer {
+	let mut config_builder = GSIConfigBuilder::new(NAME);
+
+	config_builder
+		.heartbeat(HEARTBEAT)
+		.subscribe_multiple(SUBSCRIPTIONS.map(|(subscription, _)| subscription));
+
+	GSIServer::new(config_builder.build(), port)
+}
+
+pub fn path(cfg_dir: &Path) -> PathBuf {
+	cfg_dir.join(FILE_NAME)
+}
+
+/// Something about the installed cfg that doesn't match what [`server`] would install.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Difference {
+	Missing,
+	Port { installed: Option<u16>, expected: u16 },
+	Heartbeat { installed: Option<f64> },
+	Subscription(&'static str),
+}
+
+impl fmt::Display for Difference {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Missing => write!(f, "the cfg is not installed"),
+			Self::Port { installed: Some(installed), expected } => {
+				write!(f, "port is {installed} instead of {expected}")
+			}
+			Self::Port { installed: None, expected } => {
+				write!(f, "port is missing, should be {expected}")
+			}
+			Self::Heartbeat { installed } => write!(
+				f,
+				"heartbeat is {} instead of {}s",
+				installed.map_or_else(|| String::from("missing"), |secs| format!("{secs}s")),
+				HEARTBEAT.as_secs_f64()
+			),
+			Self::Subscription(name) => write!(f, "not subscribed to `{name}`"),
+		}
+	}
+}
+
+/// Compares the installed cfg to what [`server`] would install. Empty if it's up to date.
+pub fn diff(cfg_dir: &Path, port: u16) -> Result<Vec<Difference>> {
+	let path = path(cfg_dir);
+
+	if !path.exists() {
+		return Ok(vec![Difference::Missing]);
+	}
+
+	let cfg = std::fs::read_to_string(&path).context("Failed to read GSI cfg.")?;
+	let pairs = vdf::parse(&cfg);
+
+	let value = |key: &str| {
+		pairs
+			.iter()
+			.find(|pair| pair.sections.len() == 1 && pair.key.eq_ignore_ascii_case(key))
+			.map(|pair| pair.value.trim())
+	};
+
+	let mut differences = Vec::new();
+
+	// http://127.0.0.1:8888
+	let installed_port = value("uri").and_then(|uri| {
+		uri.trim_end_matches('/')
+			.rsplit_once(':')
+			.and_then(|(_, port)| port.parse().ok())
+	});
+
+	if installed_port != Some(port) {
+		differences.push(Difference::Port { installed: installed_port, expected: port });
+	}
+
+	let heartbeat = value("heartbeat").and_then(|heartbeat| heartbeat.parse::<f64>().ok());
+
+	if heartbeat.is_none_or(|heartbeat| (heartbeat - HEARTBEAT.as_secs_f64()).abs() > 0.01) {
+		differences.push(Difference::Heartbeat { installed: heartbeat });
+	}
+
+	for (_, name) in SUBSCRIPTIONS {
+		let subscribed = pairs.iter().any(|pair| {
+			pair.sections.len() == 2
+				&& pair.sections[1].eq_ignore_ascii_case("data")
+				&& pair.key.eq_ignore_ascii_case(name)
+				&& pair.value.trim() == "1"
+		});
+
+		if !subscribed {
+			differences.push(Difference::Subscription(name));
+		}
+	}
+
+	Ok(differences)
+}
+
+/// Rewrites the cfg if it differs from what [`server`] would install.
+///
+/// Returns what was wrong with it.
+pub fn repair(cfg_dir: &Path, port: u16) -> Result<Vec<Difference>> {
+	let differences = diff(cfg_dir, port)?;
+
+	if differences.is_empty() {
+		return Ok(differences);
+	}
+
+	server(port)
+		.install_into(cfg_dir.to_path_buf())
+		.context("Failed to install GSI cfg.")?;
+
+	info!("Repaired GSI cfg in `{}`.", cfg_dir.display());
+
+	Ok(differences)
+}
+
+/// Removes the cfg. Returns whether there was one.
+pub fn uninstall(cfg_dir: &Path) -> Result<bool> {
+	let path = path(cfg_dir);
+
+	if !path.exists() {
+		return Ok(false);
+	}
+
+	std::fs::remove_file(&path).context("Failed to remove GSI cfg.")?;
+
+	info!("Removed `{}`.", path.display());
+
+	Ok(true)
+}
+
+/// `port is 8080 instead of 8888, not subscribed to `map``
+pub fn describe(differences: &[Difference]) -> String {
+	differences
+		.iter()
+		.map(ToString::to_string)
+		.collect::<Vec<_>>()
+		.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+	use {super::*, tempfile::TempDir};
+
+	/// What the game expects, with the client listening on port 8888.
+	const UP_TO_DATE: &str = r#"
+		"schnose-gsi-client"
+		{
+			"uri" "http://127.0.0.1:8888"
+			"timeout" "5.0"
+			"buffer" "0.1"
+			"throttle" "0.1"
+			"heartbeat" "1.0"
+			"data"
+			{
+				"map" "1"
+				"player_id" "1"
+			}
+		}
+	"#;
+
+	fn diff_cfg(cfg: &str, port: u16) -> Vec<Difference> {
+		let cfg_dir = TempDir::new().unwrap();
+		std::fs::write(path(cfg_dir.path()), cfg).unwrap();
+		diff(cfg_dir.path(), port).unwrap()
+	}
+
+	#[test]
+	fn up_to_date() {
+		assert!(diff_cfg(UP_TO_DATE, 8888).is_empty());
+	}
+
+	#[test]
+	fn stale_port() {
+		assert_eq!(
+			diff_cfg(UP_TO_DATE, 9000),
+			[Difference::Port { installed: Some(8888), expected: 9000 }]
+		);
+	}
+
+	#[test]
+	fn missing_data_entry() {
+		let cfg = UP_TO_DATE.replace(r#""player_id" "1""#, "");
+		assert_eq!(diff_cfg(&cfg, 8888), [Difference::Subscription("player_id")]);
+
+		let cfg = UP_TO_DATE.replace(r#""map" "1""#, r#""map" "0""#);
+		assert_eq!(diff_cfg(&cfg, 8888), [Difference::Subscription("map")]);
+	}
+
+	#[test]
+	fn missing_file() {
+		let cfg_dir = TempDir::new().unwrap();
+		assert_eq!(diff(cfg_dir.path(), 8888).unwrap(), [Difference::Missing]);
+	}
+
+	#[test]
+	fn uri_ports() {
+		for (uri, installed) in [
+			("http://127.0.0.1:8888/", Some(8888)),
+			("http://localhost:8888", Some(8888)),
+			("http://127.0.0.1:9000", Some(9000)),
+			("http://127.0.0.1", None),
+			("http://127.0.0.1/", None),
+			("", None),
+		] {
+			let cfg = UP_TO_DATE.replace("http://127.0.0.1:8888", uri);
+			let expected = match installed {
+				Some(8888) => vec![],
+				installed => vec![Difference::Port { installed, expected: 8888 }],
+			};
+
+			assert_eq!(diff_cfg(&cfg, 8888), expected, "{uri}");
+		}
+	}
+
+	#[test]
+	fn heartbeat_tolerance() {
+		for (heartbeat, installed) in [
+			("1", None),
+			("1.005", None),
+			("0.995", None),
+			(" 1.0 ", None),
+			("1.5", Some(Some(1.5))),
+			("0.1", Some(Some(0.1))),
+			("fast", Some(None)),
+		] {
+			let heartbeat_pair = format!(r#""heartbeat" "{heartbeat}""#);
+			let cfg = UP_TO_DATE.replace(r#""heartbeat" "1.0""#, &heartbeat_pair);
+			let expected = installed
+				.map(|installed| Difference::Heartbeat { installed })
+				.into_iter()
+				.collect::<Vec<_>>();
+
+			assert_eq!(diff_cfg(&cfg, 8888), expected, "{heartbeat}");
+		}
+
+		let cfg = UP_TO_DATE.replace(r#""heartbeat" "1.0""#, "");
+		assert_eq!(diff_cfg(&cfg, 8888), [Difference::Heartbeat { installed: None }]);
+	}
+
+	#[test]
+	fn subscription_depth() {
+		// Subscriptions only count inside of `data`, not at the top level or in other sections.
+		let cfg = r#"
+			"schnose-gsi-client"
+			{
+				"uri" "http://127.0.0.1:8888"
+				"heartbeat" "1.0"
+				"map" "1"
+				"auth" { "player_id" "1" }
+				"data" { "nested" { "map" "1" "player_id" "1" } }
+			}
+		"#;
+
+		assert_eq!(
+			diff_cfg(cfg, 8888),
+			[Difference::Subscription("map"), Difference::Subscription("player_id")]
+		);
+	}
+
+	#[test]
+	fn case_insensitive_keys() {
+		let cfg = r#"
+			"schnose-gsi-client"
+			{
+				"URI" "http://127.0.0.1:8888"
+				"HeartBeat" "1.0"
+				"Data"
+				{
+					"MAP" "1"
+					"Player_ID" "1"
+				}
+			}
+		"#;
+
+		assert!(diff_cfg(cfg, 8888).is_empty());
+	}
+
+	#[test]
+	fn repair_and_uninstall() {
+		let cfg_dir = TempDir::new().unwrap();
+
+		assert_eq!(repair(cfg_dir.path(), 8888).unwrap(), [Difference::Missing]);
+		assert!(diff(cfg_dir.path(), 8888).unwrap().is_empty());
+
+		// Nothing to do once it's up to date.
+		assert!(repair(cfg_dir.path(), 8888).unwrap().is_empty());
+
+		let stale = [Difference::Port { installed: Some(8888), expected: 9000 }];
+		assert_eq!(repair(cfg_dir.path(), 9000).unwrap(), stale);
+		assert!(diff(cfg_dir.path(), 9000).unwrap().is_empty());
+
+		assert!(uninstall(cfg_dir.path()).unwrap());
+		assert!(!path(cfg_dir.path()).exists());
+		assert!(!uninstall(cfg_dir.path()).unwrap());
+		assert_eq!(diff(cfg_dir.path(), 9000).unwrap(), [Difference::Missing]);
+	}
+}
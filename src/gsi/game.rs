@@ -0,0 +1,107 @@
+//! Everything that differs between CS:GO (GOKZ) and CS2 (cs2kz).
+
+use {
+	gokz_rs::Mode,
+	serde::{Deserialize, Serialize},
+	std::path::Path,
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Game {
+	#[default]
+	Csgo,
+	Cs2,
+}
+
+impl Game {
+	pub const ALL: [Self; 2] = [Self::Csgo, Self::Cs2];
+
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::Csgo => "CS:GO",
+			Self::Cs2 => "CS2",
+		}
+	}
+
+	/// Where the cfg folder lives inside of a Steam library.
+	///
+	/// CS2 replaced CS:GO in the same folder, so both can't be installed from the same library.
+	pub const fn cfg_path_in_library(self) -> &'static [&'static str] {
+		match self {
+			Self::Csgo => &[
+				"steamapps",
+				"common",
+				"Counter-Strike Global Offensive",
+				"csgo",
+				"cfg",
+			],
+			Self::Cs2 => &[
+				"steamapps",
+				"common",
+				"Counter-Strike Global Offensive",
+				"game",
+				"csgo",
+				"cfg",
+			],
+		}
+	}
+
+	/// `csgo/cfg` or `game/csgo/cfg`, for telling users what to pick.
+	pub const fn cfg_folder(self) -> &'static str {
+		match self {
+			Self::Csgo => "csgo/cfg",
+			Self::Cs2 => "game/csgo/cfg",
+		}
+	}
+
+	/// A file that sits next to the cfg folder in a real install.
+	const fn marker_file(self) -> &'static str {
+		match self {
+			Self::Csgo => "gameinfo.txt",
+			Self::Cs2 => "gameinfo.gi",
+		}
+	}
+
+	/// Whether `path` is the cfg folder of an install of this game.
+	pub fn looks_like_cfg_folder(self, path: &Path) -> bool {
+		let is_cfg = path.file_name().is_some_and(|name| name == "cfg");
+		let has_marker = path
+			.parent()
+			.is_some_and(|game_dir| game_dir.join(self.marker_file()).is_file());
+
+		is_cfg && has_marker
+	}
+
+	/// Prefixes of maps the overlay cares about. Everything else shows up as "unknown map".
+	pub const fn map_prefixes(self) -> &'static [&'static str] {
+		match self {
+			Self::Csgo => &["bkz_", "kz_", "kzpro_", "skz_", "vnl_", "xc_"],
+			Self::Cs2 => &["bkz_", "kz_", "skz_", "vnl_", "xc_"],
+		}
+	}
+
+	pub fn is_valid_map_name(self, map_name: &str) -> bool {
+		self.map_prefixes()
+			.iter()
+			.any(|prefix| map_name.starts_with(prefix))
+	}
+
+	/// Reads the mode out of the player's clan tag.
+	///
+	/// GOKZ sets it to `[KZT Rank]`, cs2kz to `[CKZ]` or `[VNL]`.
+	pub fn parse_mode(self, clan: &str) -> Option<Mode> {
+		let tag = clan.replace(['[', ']'], "");
+		let mode = tag.split(' ').next()?;
+
+		match self {
+			Self::Csgo => mode.parse().ok(),
+			Self::Cs2 => match mode.to_ascii_uppercase().as_str() {
+				// Classic is cs2kz' take on SimpleKZ, and the closest GOKZ mode for records.
+				"CKZ" | "CLASSIC" => Some(Mode::SimpleKZ),
+				"VNL" | "VANILLA" => Some(Mode::Vanilla),
+				_ => None,
+			},
+		}
+	}
+}
@@ -1,63 +1,105 @@
 use {
-	crate::config::Config,
-	color_eyre::{eyre::Context, Result},
+	crate::{config::Config, metrics::METRICS, status::Tracker},
+	chrono::{DateTime, Utc},
+	color_eyre::{
+		eyre::{bail as yeet, Context},
+		Result,
+	},
 	gokz_rs::{global_api, MapIdentifier, Mode, SteamID, Tier},
-	schnose_gsi::{GSIConfigBuilder, GSIServer, Subscription},
+	schemars::JsonSchema,
 	serde::{Deserialize, Serialize},
-	std::{sync::Arc, time::Duration},
-	tokio::sync::{broadcast::Sender, Mutex},
+	std::{collections::VecDeque, sync::Arc},
+	tokio::sync::{broadcast::Sender, oneshot, Mutex},
 	tracing::{debug, error, info, trace, warn},
 	uuid::Uuid,
 };
 
+pub mod cfg;
+
+mod game;
+pub use game::Game;
+
+/// Shown instead of the map name in menus and on maps the overlay doesn't care about.
+pub const UNKNOWN_MAP: &str = "unknown map";
+
+/// How many events [`recent_events`] keeps around.
+const RECENT_EVENTS: usize = 20;
+
+static RECENT: std::sync::Mutex<VecDeque<(DateTime<Utc>, String)>> =
+	std::sync::Mutex::new(VecDeque::new());
+
+fn remember_event(event: String) {
+	if let Ok(mut recent) = RECENT.lock() {
+		if recent.len() >= RECENT_EVENTS {
+			recent.pop_front();
+		}
+
+		recent.push_back((Utc::now(), event));
+	}
+}
+
+/// The last few events CS:GO sent, oldest first, for [`crate::diagnose`].
+pub fn recent_events() -> Vec<(DateTime<Utc>, String)> {
+	RECENT
+		.lock()
+		.map(|recent| recent.iter().cloned().collect())
+		.unwrap_or_default()
+}
+
 pub fn run(
 	// state: Arc<Mutex<Option<State>>>,
 	state_sender: Sender<State>,
 	config: Arc<Mutex<Config>>,
-) -> Result<schnose_gsi::ServerHandle> {
-	let mut config_builder = GSIConfigBuilder::new("schnose-gsi-client");
-
-	config_builder
-		.heartbeat(Duration::from_secs(1))
-		.subscribe_multiple([
-			Subscription::Map,
-			Subscription::PlayerID,
-		]);
-
-	let gsi_config = config_builder.build();
-
-	let (port, detect_install_dir) = tokio::task::block_in_place(|| {
+	tracker: Arc<Mutex<Tracker>>,
+) -> Result<oneshot::Sender<()>> {
+	let (port, game, detect_install_dir) = tokio::task::block_in_place(|| {
 		let config = config.blocking_lock();
-		let is_fake = match &config.csgo_cfg_path {
+		let is_fake = match config.cfg_path() {
 			None => true,
 			Some(path) => !path.exists(),
 		};
-		let is_cwd = match &config.csgo_cfg_path {
+		let is_cwd = match config.cfg_path() {
 			None => true,
 			Some(path) => path.as_os_str().is_empty(),
 		};
 
-		(config.gsi_port, is_fake || is_cwd)
+		(config.gsi_port, config.game, is_fake || is_cwd)
 	});
 
-	let mut gsi_server = GSIServer::new(gsi_config, port);
+	let mut gsi_server = cfg::server(port);
+
+	let cfg_dir = if detect_install_dir {
+		let Some(cfg_dir) = crate::steam::find_cfg_dirs(game).into_iter().next() else {
+			yeet!("Failed to locate cfg directory automatically.");
+		};
+
+		info!("Found cfg directory at `{}`.", cfg_dir.display());
 
-	if detect_install_dir {
-		gsi_server
-			.install()
-			.context("Failed to locate cfg directory automatically.")?;
+		cfg_dir
 	} else {
-		gsi_server
-			.install_into(tokio::task::block_in_place(|| {
-				config
-					.blocking_lock()
-					.csgo_cfg_path
-					.clone()
-					.expect("Config directory may not be empty")
-			}))
-			.context("Failed to install GSI config. Did you enter the correct directory?")?;
+		tokio::task::block_in_place(|| {
+			config
+				.blocking_lock()
+				.cfg_path()
+				.cloned()
+				.expect("Config directory may not be empty")
+		})
+	};
+
+	match cfg::diff(&cfg_dir, port) {
+		Ok(differences) if !differences.is_empty() => warn!(
+			"Updating GSI cfg ({}). Restart {} if it's already running.",
+			cfg::describe(&differences),
+			game.name()
+		),
+		Ok(_) => {}
+		Err(why) => warn!("Failed to check installed GSI cfg: {why:?}"),
 	}
 
+	gsi_server
+		.install_into(cfg_dir)
+		.context("Failed to install GSI config. Did you enter the correct directory?")?;
+
 	let state_sender = Arc::new(state_sender);
 	let gokz_client = Arc::new(gokz_rs::Client::new());
 	let prev_event = Arc::new(Mutex::new(None));
@@ -67,15 +109,22 @@ pub fn run(
 		error!("Failed to send new state: {why:?}");
 	}
 
+	let server_tracker = Arc::clone(&tracker);
+
 	gsi_server.add_async_event_listener(move |event| {
 		let gokz_client = Arc::clone(&gokz_client);
 		let state_sender = Arc::clone(&state_sender);
 		let config = Arc::clone(&config);
 		let prev_event = Arc::clone(&prev_event);
+		let tracker = Arc::clone(&tracker);
 
 		Box::pin(async move {
 			trace!("New GSI Event.");
 			debug!("{event:#?}");
+			remember_event(format!("{event:#?}"));
+
+			tracker.lock().await.received_event();
+			METRICS.gsi_event_received();
 
 			// Check if the new event is the same as the previous one.
 			// There is no need to proceed and re-fetch information from the GlobalAPI if nothing
@@ -84,14 +133,17 @@ pub fn run(
 				let mut prev_event = prev_event.lock().await;
 				if (*prev_event).as_ref() == Some(&event) {
 					warn!("SAME EVENT");
-					return;
+					return METRICS.gsi_event_deduplicated();
 				}
 				*prev_event = Some(event.clone());
 			}
 
-			let new_state = match State::from_event(event, &gokz_client).await {
+			let new_state = match State::from_event(event, game, &gokz_client).await {
 				Ok(state) => state,
-				Err(why) => return error!("Failed to parse event: {why:#?}"),
+				Err(why) => {
+					METRICS.gsi_event_failed();
+					return error!("Failed to parse event: {why:#?}");
+				}
 			};
 
 			info!("Sending state: {new_state:?}");
@@ -106,38 +158,79 @@ pub fn run(
 			};
 
 			let Some(schnose_api_key) = schnose_api_key else {
-				return;
+				return tracker.lock().await.twitch_bot_disabled();
 			};
 
-			if let Err(why) =
-				notify_twitch_bot(new_state, &api_url, schnose_api_key, &gokz_client).await
-			{
+			let result =
+				notify_twitch_bot(&new_state, &api_url, schnose_api_key, &gokz_client).await;
+
+			if let Err(why) = &result {
 				error!("Failed to notify Twitch Bot with new state: {why:#?}");
 			}
+
+			tracker.lock().await.twitch_bot_notified(result);
 		})
 	});
 
-	info!("Listening for CS:GO events on port {port}.");
+	info!("Listening for {} events on port {port}.", game.name());
 
-	gsi_server
+	let handle = gsi_server
 		.run()
-		.context("Failed to run GSI Server.")
+		.context("Failed to run GSI Server.")?;
+
+	tokio::task::block_in_place(|| server_tracker.blocking_lock().gsi_listening(true));
+
+	let (shutdown_sender, shutdown_receiver) = oneshot::channel();
+	tokio::spawn(supervise(handle, server_tracker, shutdown_receiver));
+
+	Ok(shutdown_sender)
 }
 
-async fn notify_twitch_bot(
-	state: State,
+/// Stops the GSI server when dropped, even if [`supervise`] gets cancelled.
+struct AbortOnDrop(Option<schnose_gsi::ServerHandle>);
+
+impl Drop for AbortOnDrop {
+	fn drop(&mut self) {
+		if let Some(handle) = self.0.take() {
+			handle.abort();
+		}
+	}
+}
+
+/// Keeps the GSI server running until `shutdown` fires or its sender is dropped, then stops it
+/// and reports it as no longer listening.
+async fn supervise(
+	handle: schnose_gsi::ServerHandle,
+	tracker: Arc<Mutex<Tracker>>,
+	shutdown: oneshot::Receiver<()>,
+) {
+	let handle = AbortOnDrop(Some(handle));
+
+	// An error only means the sender is gone, which is a shutdown too.
+	let _ = shutdown.await;
+	drop(handle);
+
+	info!("Stopped GSI server.");
+	tracker.lock().await.gsi_listening(false);
+}
+
+pub async fn notify_twitch_bot(
+	payload: &impl Serialize,
 	api_url: &str,
 	api_key: Uuid,
 	gokz_client: &gokz_rs::Client,
 ) -> Result<()> {
-	match gokz_client
+	let result = gokz_client
 		.post(api_url)
-		.json(&state)
+		.json(payload)
 		.header("x-schnose-api-key", api_key.to_string())
 		.send()
 		.await
-		.map(|res| res.error_for_status())
-	{
+		.map(|res| res.error_for_status());
+
+	METRICS.twitch_bot_notified(matches!(result, Ok(Ok(_))));
+
+	match result {
 		Ok(Ok(res)) => {
 			trace!("Notified Twitch Bot!");
 			debug!("{res:#?}");
@@ -151,22 +244,22 @@ async fn notify_twitch_bot(
 	}
 }
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct State {
 	pub player_name: Option<String>,
+	#[schemars(with = "Option<String>")]
 	pub steam_id: Option<SteamID>,
 	pub map_name: Option<String>,
+	#[schemars(with = "Option<u8>")]
 	pub map_tier: Option<Tier>,
+	#[schemars(with = "Option<String>")]
 	pub mode: Option<Mode>,
 }
 
 impl State {
-	const VALID_MAP_PREFIXES: [&str; 6] = [
-		"bkz_", "kz_", "kzpro_", "skz_", "vnl_", "xc_",
-	];
-
 	pub async fn from_event(
 		event: schnose_gsi::Event,
+		game: Game,
 		gokz_client: &gokz_rs::Client,
 	) -> Result<Self> {
 		let (player_name, steam_id, mode) = event
@@ -176,19 +269,7 @@ impl State {
 				let steam_id = player.steam_id;
 				let mode = player
 					.clan
-					.and_then(|clan| match clan.contains(' ') {
-						true => clan
-							.split_once(' ')
-							.and_then(|(mode, _rank)| {
-								mode.replace('[', "")
-									.parse::<Mode>()
-									.ok()
-							}),
-						false => clan
-							.replace(['[', ']'], "")
-							.parse::<Mode>()
-							.ok(),
-					});
+					.and_then(|clan| game.parse_mode(&clan));
 
 				(Some(name), Some(steam_id), mode)
 			})
@@ -204,14 +285,28 @@ impl State {
 					.unwrap(),
 				false => map.name.clone(),
 			}) {
-			None => (String::from("unknown map"), None),
-			Some(map_name) if !Self::is_valid_map_name(&map_name) => {
-				(String::from("unknown map"), None)
+			None => (String::from(UNKNOWN_MAP), None),
+			Some(map_name) if !game.is_valid_map_name(&map_name) => {
+				(String::from(UNKNOWN_MAP), None)
+			}
+			Some(map_name) => {
+				let map_identifier = MapIdentifier::Name(map_name.clone());
+				let request = global_api::get_map(&map_identifier, gokz_client);
+
+				match game {
+					Game::Csgo => METRICS
+						.global_api("get_map", request)
+						.await
+						.map(|map| (map.name, Some(map.difficulty)))
+						.context("Failed to fetch map from GlobalAPI.")?,
+					// Most CS2 maps aren't on the GlobalAPI (yet), which is fine and shouldn't
+					// show up as failed requests in the metrics.
+					Game::Cs2 => match request.await {
+						Ok(map) => (map.name, Some(map.difficulty)),
+						Err(_) => (map_name, None),
+					},
+				}
 			}
-			Some(map_name) => global_api::get_map(&MapIdentifier::Name(map_name), gokz_client)
-				.await
-				.map(|map| (map.name, Some(map.difficulty)))
-				.context("Failed to fetch map from GlobalAPI.")?,
 		};
 
 		Ok(Self {
@@ -223,9 +318,19 @@ impl State {
 		})
 	}
 
-	fn is_valid_map_name(map_name: &str) -> bool {
-		Self::VALID_MAP_PREFIXES
-			.iter()
-			.any(|prefix| map_name.starts_with(prefix))
+	/// The current map, unless it's just the [`UNKNOWN_MAP`] placeholder.
+	pub fn known_map(&self) -> Option<&str> {
+		self.map_name
+			.as_deref()
+			.filter(|&map_name| map_name != UNKNOWN_MAP)
+	}
+
+	/// `[KZT]`, `[SKZ]` and `[VNL]`, like in-game.
+	pub const fn mode_short(mode: Mode) -> &'static str {
+		match mode {
+			Mode::KZTimer => "KZT",
+			Mode::SimpleKZ => "SKZ",
+			Mode::Vanilla => "VNL",
+		}
 	}
 }
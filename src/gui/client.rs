@@ -1,40 +1,64 @@
 use {
-	super::Tab,
+	super::{
+		doctor::Doctor,
+		editor::Preview,
+		logs::{LogExport, LogFilter},
+		Tab,
+	},
 	crate::{
-		colors,
+		assets, colors,
 		config::Config,
-		logger::{Log, LogReceiver},
+		gsi::{self, Game},
+		history::History,
+		logger::{LogLevel, LogReceiver},
+		server::Receivers,
+		session::Session,
+		status::Tracker,
 	},
-	chrono::Utc,
 	eframe::{
 		egui::{
-			style::Selection, Align, Button, FontData, FontDefinitions, Layout, RichText, Style,
-			TextEdit, TextStyle, Ui, Visuals,
+			style::Selection, Button, ComboBox, FontData, FontDefinitions, RichText, Style, TextEdit,
+			TextStyle, Ui, Visuals,
 		},
 		epaint::{FontFamily, FontId},
 		CreationContext,
 	},
 	eframe::{HardwareAcceleration, NativeOptions, Theme},
-	egui_extras::{Column, TableBuilder},
 	egui_notify::Toasts,
 	rfd::FileDialog,
-	std::{collections::BTreeMap, fs::File, sync::Arc, time::Duration},
+	std::{collections::BTreeMap, path::PathBuf, sync::Arc, time::Duration},
 	tokio::{
-		sync::{broadcast, Mutex},
+		sync::{broadcast, oneshot, Mutex},
 		task::JoinHandle,
 	},
-	tracing::{error, info, warn},
+	tracing::{error, warn},
 	uuid::Uuid,
 };
 
 pub struct Client {
 	pub config: Arc<Mutex<Config>>,
 	pub logger: Option<LogReceiver>,
+	pub log_filter: LogFilter,
+	pub log_export: LogExport,
+	pub log_level: LogLevel,
+	/// What's currently typed into the log level field in the Logs tab.
+	pub log_level_prompt: String,
 	pub current_tab: Tab,
 	pub notifications: Toasts,
 	pub api_key_prompt: String,
-	pub gsi_handle: Option<schnose_gsi::ServerHandle>,
+	/// `csgo/cfg` folders found in the user's Steam libraries.
+	pub cfg_candidates: Vec<PathBuf>,
+	/// Stops the GSI server once it's sent to or dropped.
+	pub gsi_handle: Option<oneshot::Sender<()>>,
 	pub axum_handle: Option<JoinHandle<()>>,
+	pub records_handle: Option<JoinHandle<()>>,
+	pub text_files_handle: Option<JoinHandle<()>>,
+	pub history_handle: Option<JoinHandle<()>>,
+	pub preview: Option<Preview>,
+	pub session: Arc<Mutex<Session>>,
+	/// Only available while the server is running.
+	pub tracker: Option<Arc<Mutex<Tracker>>>,
+	pub doctor: Doctor,
 }
 
 impl Client {
@@ -45,7 +69,13 @@ impl Client {
 	pub const NOTIFICATION_DURATION: Option<Duration> = Some(Duration::from_secs(3));
 
 	#[tracing::instrument]
-	pub async fn init(config: Config, logger: Option<LogReceiver>) {
+	pub async fn init(mut config: Config, mut logger: Option<LogReceiver>, log_level: LogLevel) {
+		if let Some(logger) = &mut logger {
+			logger.set_capacity(config.log_capacity);
+		}
+
+		let cfg_candidates = Self::detect_cfg_dirs(&mut config);
+
 		let api_key_prompt = config
 			.schnose_api_key
 			.map(|uuid| uuid.to_string())
@@ -54,11 +84,23 @@ impl Client {
 		let client = Self {
 			config: Arc::new(Mutex::new(config)),
 			logger,
+			log_filter: LogFilter::default(),
+			log_export: LogExport::default(),
+			log_level_prompt: log_level.current(),
+			log_level,
 			current_tab: Tab::Main,
 			notifications: Toasts::default(),
 			api_key_prompt,
+			cfg_candidates,
 			gsi_handle: None,
 			axum_handle: None,
+			records_handle: None,
+			text_files_handle: None,
+			history_handle: None,
+			preview: None,
+			session: Arc::new(Mutex::new(Session::default())),
+			tracker: None,
+			doctor: Doctor::default(),
 		};
 
 		let native_options = NativeOptions {
@@ -95,12 +137,12 @@ impl Client {
 
 		font_definitions.font_data.insert(
 			String::from(Self::DEFAULT_FONT),
-			FontData::from_static(include_bytes!("../../assets/fonts/quicksand.ttf")),
+			FontData::from_static(assets::QUICKSAND),
 		);
 
 		font_definitions.font_data.insert(
 			String::from(Self::MONOSPACE_FONT),
-			FontData::from_static(include_bytes!("../../assets/fonts/firacode.ttf")),
+			FontData::from_static(assets::FIRA_CODE),
 		);
 
 		font_definitions
@@ -154,14 +196,17 @@ impl Client {
 		self.gsi_handle.is_some() && self.axum_handle.is_some()
 	}
 
-	fn spacing(ui: &mut Ui) {
+	pub(super) fn spacing(ui: &mut Ui) {
 		ui.add_space(Self::DEFAULT_SPACING);
 	}
 
 	pub fn render_main(&mut self, ui: &mut Ui) {
 		ui.vertical_centered(|ui| {
 			self.render_cfg_prompt(ui);
+			self.render_cfg_actions(ui);
+			self.render_text_files_prompt(ui);
 			self.render_key_prompt(ui);
+			self.render_theme_picker(ui);
 		});
 
 		Self::spacing(ui);
@@ -170,27 +215,183 @@ impl Client {
 
 		ui.vertical_centered(|ui| self.render_run_button(ui));
 		Self::spacing(ui);
+
+		self.render_doctor(ui);
+	}
+
+	/// Looks for installs of the selected game and picks the first one if no folder is set yet.
+	fn detect_cfg_dirs(config: &mut Config) -> Vec<PathBuf> {
+		let cfg_dirs = crate::steam::find_cfg_dirs(config.game);
+
+		let has_path = config
+			.cfg_path()
+			.is_some_and(|path| !path.as_os_str().is_empty());
+
+		if !has_path {
+			*config.cfg_path_mut() = cfg_dirs.first().cloned();
+		}
+
+		cfg_dirs
+	}
+
+	fn render_game_picker(&mut self, ui: &mut Ui) {
+		let config = &mut *tokio::task::block_in_place(|| self.config.blocking_lock());
+		let previous = config.game;
+
+		ComboBox::from_id_source("game-picker")
+			.selected_text(config.game.name())
+			.show_ui(ui, |ui| {
+				for game in Game::ALL {
+					ui.selectable_value(&mut config.game, game, game.name());
+				}
+			});
+
+		if config.game != previous {
+			self.cfg_candidates = Self::detect_cfg_dirs(config);
+		}
 	}
 
 	fn render_cfg_prompt(&mut self, ui: &mut Ui) {
-		let button = ui.add(Button::new("Select your /csgo/cfg folder").fill(colors::SURFACE2));
+		self.render_game_picker(ui);
 
 		let config = &mut *tokio::task::block_in_place(|| self.config.blocking_lock());
 
+		let text = format!("Select your /{} folder", config.game.cfg_folder());
+		let button = ui.add(Button::new(text).fill(colors::SURFACE2));
+
 		if button.clicked() {
 			if let Some(new_cfg_path) = FileDialog::new().pick_folder() {
-				config.csgo_cfg_path = Some(new_cfg_path);
+				*config.cfg_path_mut() = Some(new_cfg_path);
 			}
 		}
 
 		button.on_hover_text(format!(
 			"Current folder: {}",
 			config
-				.csgo_cfg_path
-				.as_ref()
+				.cfg_path()
 				.map(|path| path.display().to_string())
 				.unwrap_or_default()
 		));
+
+		if self.cfg_candidates.is_empty() {
+			return;
+		}
+
+		let selected = match config.cfg_path() {
+			Some(path) if self.cfg_candidates.contains(path) => path.display().to_string(),
+			_ => String::from("Detected installs"),
+		};
+
+		ComboBox::from_id_source("cfg-candidates")
+			.selected_text(selected)
+			.show_ui(ui, |ui| {
+				for candidate in &self.cfg_candidates {
+					let label = candidate.display().to_string();
+					ui.selectable_value(config.cfg_path_mut(), Some(candidate.clone()), label);
+				}
+			});
+	}
+
+	fn render_cfg_actions(&mut self, ui: &mut Ui) {
+		let (cfg_dir, port, game) = {
+			let config = tokio::task::block_in_place(|| self.config.blocking_lock());
+			(config.cfg_path().cloned(), config.gsi_port, config.game.name())
+		};
+
+		let Some(cfg_dir) = cfg_dir.filter(|path| !path.as_os_str().is_empty()) else {
+			return;
+		};
+
+		let restart = format!("Restart {game} to pick up the changes.");
+
+		ui.horizontal(|ui| {
+			if ui
+				.add(Button::new("Repair GSI cfg").fill(colors::SURFACE0))
+				.on_hover_text("Rewrites the cfg if its port or settings are outdated.")
+				.clicked()
+			{
+				match gsi::cfg::repair(&cfg_dir, port) {
+					Ok(differences) if differences.is_empty() => {
+						self.notifications
+							.info("The GSI cfg is up to date.")
+							.set_duration(Self::NOTIFICATION_DURATION);
+					}
+					Ok(differences) => {
+						self.notifications
+							.warning(format!(
+								"Repaired GSI cfg: {}. {restart}",
+								gsi::cfg::describe(&differences)
+							))
+							.set_duration(Self::NOTIFICATION_DURATION)
+							.set_closable(true);
+					}
+					Err(why) => {
+						error!("Failed to repair GSI cfg: {why:?}");
+						self.notifications
+							.error(format!("{why}"))
+							.set_duration(Self::NOTIFICATION_DURATION);
+					}
+				}
+			}
+
+			if ui
+				.add_enabled(
+					!self.server_running(),
+					Button::new("Uninstall GSI cfg").fill(colors::SURFACE0),
+				)
+				.on_hover_text(format!("Stops {game} from sending events to the client."))
+				.on_disabled_hover_text("Stop the server first.")
+				.clicked()
+			{
+				match gsi::cfg::uninstall(&cfg_dir) {
+					Ok(true) => {
+						self.notifications
+							.warning(format!("Removed GSI cfg. {restart}"))
+							.set_duration(Self::NOTIFICATION_DURATION)
+							.set_closable(true);
+					}
+					Ok(false) => {
+						self.notifications
+							.info("There is no GSI cfg to remove.")
+							.set_duration(Self::NOTIFICATION_DURATION);
+					}
+					Err(why) => {
+						error!("Failed to uninstall GSI cfg: {why:?}");
+						self.notifications
+							.error(format!("{why}"))
+							.set_duration(Self::NOTIFICATION_DURATION);
+					}
+				}
+			}
+		});
+	}
+
+	fn render_text_files_prompt(&mut self, ui: &mut Ui) {
+		let config = &mut *tokio::task::block_in_place(|| self.config.blocking_lock());
+
+		ui.horizontal(|ui| {
+			let button =
+				ui.add(Button::new("Select a folder for text files").fill(colors::SURFACE2));
+
+			if button.clicked() {
+				if let Some(new_dir) = FileDialog::new().pick_folder() {
+					config.text_files_dir = Some(new_dir);
+				}
+			}
+
+			button.on_hover_text(match &config.text_files_dir {
+				Some(dir) => format!(
+					"Writes {} files into {} for OBS text sources.",
+					config.text_files.len(),
+					dir.display()
+				),
+				None => String::from("Writes map, tier, WRs etc. into files for OBS text sources."),
+			});
+
+			if config.text_files_dir.is_some() && ui.button("Disable").clicked() {
+				config.text_files_dir = None;
+			}
+		});
 	}
 
 	fn render_key_prompt(&mut self, ui: &mut Ui) {
@@ -212,6 +413,44 @@ impl Client {
 		}
 	}
 
+	fn render_theme_picker(&mut self, ui: &mut Ui) {
+		const BUILT_IN: &str = "Built-in";
+
+		ui.label("Overlay theme: ");
+
+		let config = &mut *tokio::task::block_in_place(|| self.config.blocking_lock());
+
+		let response = ComboBox::from_id_source("theme-picker")
+			.selected_text(config.overlay_theme.as_deref().unwrap_or(BUILT_IN))
+			.show_ui(ui, |ui| {
+				ui.selectable_value(&mut config.overlay_theme, None, BUILT_IN);
+
+				// Only read the themes folder while the dropdown is open.
+				match crate::server::themes::list() {
+					Ok(themes) => {
+						for theme in themes {
+							let label = theme.clone();
+							ui.selectable_value(&mut config.overlay_theme, Some(theme), label);
+						}
+					}
+					Err(why) => {
+						ui.colored_label(colors::RED, format!("{why}"));
+					}
+				}
+			});
+
+		if response.response.hovered() {
+			if let Ok(themes_dir) = Config::themes_dir() {
+				response
+					.response
+					.on_hover_text(format!("Themes folder: {}", themes_dir.display()));
+			}
+		}
+
+		ui.checkbox(&mut config.overlay_live_reload, "Live-reload overlay")
+			.on_hover_text("Reloads the overlay whenever one of the theme's files changes.");
+	}
+
 	fn render_run_button(&mut self, ui: &mut Ui) {
 		if self.server_running() {
 			let stop_text = RichText::new("Stop GSI Server").color(colors::RED);
@@ -227,7 +466,7 @@ impl Client {
 				{
 					let config = tokio::task::block_in_place(|| self.config.blocking_lock());
 
-					let has_path = match &config.csgo_cfg_path {
+					let has_path = match config.cfg_path() {
 						None => false,
 						Some(path) if path.as_os_str().is_empty() => false,
 						_ => true,
@@ -238,6 +477,8 @@ impl Client {
 							.error("You need to enter a cfg path before you can start the server.")
 							.set_duration(Self::NOTIFICATION_DURATION)
 							.set_closable(true);
+						drop(config);
+						self.run_doctor();
 						return;
 					}
 				}
@@ -249,8 +490,34 @@ impl Client {
 
 	fn run_server(&mut self) {
 		let (state_sender, state_receiver) = broadcast::channel(64);
+		let (records_sender, records_receiver) = broadcast::channel(16);
+		let (event_sender, event_receiver) = broadcast::channel(16);
+		let watcher_receiver = state_sender.subscribe();
+		let text_files_state_receiver = state_sender.subscribe();
+		let text_files_records_receiver = records_sender.subscribe();
+		let preview = Preview::new(state_sender.subscribe(), records_sender.subscribe());
+		let history_receiver = state_sender.subscribe();
+		let tracker = Arc::new(Mutex::new(Tracker::default()));
+
+		let history_config =
+			tokio::task::block_in_place(|| self.config.blocking_lock().history.clone());
+
+		let history = match history_config.enabled {
+			false => None,
+			true => match History::open() {
+				Ok(history) => Some(Arc::new(history)),
+				Err(why) => {
+					error!("Failed to open history: {why:?}");
+					None
+				}
+			},
+		};
 
-		self.gsi_handle = match crate::gsi::run(state_sender, Arc::clone(&self.config)) {
+		self.gsi_handle = match crate::gsi::run(
+			state_sender,
+			Arc::clone(&self.config),
+			Arc::clone(&tracker),
+		) {
 			Ok(handle) => {
 				self.notifications
 					.info("Starting GSI Server...")
@@ -261,17 +528,70 @@ impl Client {
 				self.notifications
 					.error(format!("{why}"))
 					.set_duration(Self::NOTIFICATION_DURATION);
+				self.run_doctor();
 				return;
 			}
 		};
 
-		self.axum_handle = Some(tokio::spawn(crate::server::run(state_receiver)));
+		self.preview = Some(preview);
+		self.tracker = Some(Arc::clone(&tracker));
+
+		if let Some(history) = &history {
+			self.history_handle = Some(tokio::spawn(crate::history::watch(
+				history_receiver,
+				Arc::clone(history),
+				history_config.retention_days,
+			)));
+		}
+
+		self.records_handle = Some(tokio::spawn(crate::records::watch(
+			watcher_receiver,
+			records_sender,
+			event_sender,
+			Arc::clone(&self.config),
+			Arc::clone(&tracker),
+			history.clone(),
+		)));
+
+		self.text_files_handle = Some(tokio::spawn(crate::text_files::watch(
+			text_files_state_receiver,
+			text_files_records_receiver,
+			Arc::clone(&self.config),
+		)));
+
+		self.axum_handle = Some(tokio::spawn(crate::server::run(
+			Receivers {
+				state: state_receiver,
+				records: records_receiver,
+				events: event_receiver,
+			},
+			tracker,
+			Arc::clone(&self.session),
+			history,
+			self.log_level.clone(),
+			Arc::clone(&self.config),
+		)));
 		self.notifications
 			.info("Starting HTTP Server...")
 			.set_duration(Self::NOTIFICATION_DURATION);
 	}
 
 	fn stop_server(&mut self) {
+		self.preview = None;
+		self.tracker = None;
+
+		if let Some(handle) = self.records_handle.take() {
+			handle.abort();
+		}
+
+		if let Some(handle) = self.text_files_handle.take() {
+			handle.abort();
+		}
+
+		if let Some(handle) = self.history_handle.take() {
+			handle.abort();
+		}
+
 		if let Some(handle) = self.axum_handle.take() {
 			handle.abort();
 			self.notifications
@@ -279,81 +599,15 @@ impl Client {
 				.set_duration(Self::NOTIFICATION_DURATION);
 		}
 
-		if let Some(handle) = self.gsi_handle.take() {
-			handle.abort();
+		if let Some(shutdown) = self.gsi_handle.take() {
+			// Only fails if the GSI server is gone already.
+			let _ = shutdown.send(());
 			self.notifications
 				.info("Stopping GSI Server...")
 				.set_duration(Self::NOTIFICATION_DURATION);
 		}
 	}
 
-	pub fn render_logs(&mut self, ui: &mut Ui) {
-		let Some(logger) = &mut self.logger else {
-			ui.vertical_centered(|ui| ui.colored_label(colors::RED, "Logs are displayed on STDOUT."));
-			return;
-		};
-
-		let logs = logger.current();
-		let logs = Log::from_slice(&logs);
-
-		let button = {
-			let mut button = None;
-
-			ui.horizontal(|ui| {
-				self.save_logs(ui);
-				let jump_button = Button::new("Go to bottom").fill(colors::SURFACE0);
-				button = Some(ui.add(jump_button));
-			});
-
-			ui.add_space(Self::DEFAULT_SPACING);
-			ui.separator();
-			ui.add_space(Self::DEFAULT_SPACING);
-
-			button.unwrap()
-		};
-
-		let mut table = TableBuilder::new(ui)
-			.striped(true)
-			.resizable(false)
-			.stick_to_bottom(true)
-			.cell_layout(Layout::left_to_right(Align::TOP))
-			.column(Column::auto())
-			.column(Column::auto())
-			.column(Column::remainder())
-			.vscroll(true)
-			.min_scrolled_height(0.0);
-
-		if button.clicked() {
-			table = table.scroll_to_row(logs.len(), None);
-		}
-
-		table.body(|body| {
-			let heights = logs
-				.iter()
-				.map(|log| {
-					let lines = log.message.text().lines().count();
-					lines as f32 * Self::DEFAULT_SPACING * 4.0
-				})
-				.collect::<Vec<_>>();
-
-			body.heterogeneous_rows(heights.into_iter(), |idx, mut row| {
-				if let Some(log) = logs.get(idx).cloned() {
-					row.col(|ui| {
-						ui.label(log.timestamp);
-					});
-
-					row.col(|ui| {
-						ui.label(log.level);
-					});
-
-					row.col(|ui| {
-						ui.label(log.message);
-					});
-				}
-			});
-		});
-	}
-
 	pub fn render_status(&self, ui: &mut Ui) {
 		if self.server_running() {
 			ui.scope(|ui| {
@@ -361,46 +615,11 @@ impl Client {
 				ui.label(RichText::new("Running").color(colors::GREEN));
 				ui.hyperlink_to(
 					"Open Overlay",
-					format!("http://localhost:{}", crate::server::PORT),
+					format!("http://localhost:{}/overlay", crate::server::PORT),
 				);
 			});
 		} else {
 			ui.label(RichText::new("Stopped").color(colors::RED));
 		}
 	}
-
-	fn save_logs(&mut self, ui: &mut Ui) {
-		use std::io::Write;
-
-		let text = RichText::new("Save logs").color(colors::TEXT);
-		let button = Button::new(text).fill(colors::SURFACE0);
-
-		if !ui.add(button).clicked() {
-			return;
-		}
-
-		let timestamp = Utc::now().format("%Y%m%d%H%M%S");
-		let file_name = format!("{timestamp}-schnose-gsi-client.log");
-
-		let Some(log_path) = FileDialog::new().set_file_name(&file_name).save_file() else {
-			return;
-		};
-
-		let mut file = match File::create(&log_path) {
-			Ok(file) => file,
-			Err(why) => return error!("Failed to create log file: {why:#?}"),
-		};
-
-		let Some(logger) = &mut self.logger else {
-			return error!("This UI should only be rendered if a logger is present.");
-		};
-
-		let logs = logger.current();
-		let log_path = log_path.display();
-
-		match file.write_all(&logs) {
-			Ok(()) => info!("Wrote logs to `{log_path}`."),
-			Err(why) => error!("Failed to write logs to `{log_path}`: {why:#?}"),
-		}
-	}
 }
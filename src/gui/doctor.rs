@@ -0,0 +1,145 @@
+//! The setup checklist on the "Main" tab.
+
+use {
+	super::Client,
+	crate::{
+		colors,
+		doctor::{self, Check, Outcome},
+	},
+	eframe::egui::{Button, CollapsingHeader, Grid, RichText, Ui},
+	std::time::Duration,
+	tokio::sync::oneshot::{self, error::TryRecvError},
+};
+
+/// How often the first event check is repeated while it's pending.
+const FIRST_EVENT_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Default)]
+pub struct Doctor {
+	checks: Vec<Check>,
+	/// Checks that are still running in the background.
+	pending: Option<oneshot::Receiver<Vec<Check>>>,
+	open: bool,
+}
+
+impl Client {
+	/// Runs every check in the background and opens the checklist.
+	pub(super) fn run_doctor(&mut self) {
+		let config = tokio::task::block_in_place(|| self.config.blocking_lock()).clone();
+		let tracker = self.tracker.clone();
+		let (sender, receiver) = oneshot::channel();
+
+		tokio::spawn(async move {
+			let checks = doctor::run(&config, tracker.as_deref()).await;
+			let _ = sender.send(checks);
+		});
+
+		self.doctor.pending = Some(receiver);
+		self.doctor.open = true;
+	}
+
+	pub(super) fn render_doctor(&mut self, ui: &mut Ui) {
+		if let Some(receiver) = &mut self.doctor.pending {
+			match receiver.try_recv() {
+				Ok(checks) => {
+					self.doctor.checks = checks;
+					self.doctor.pending = None;
+				}
+				Err(TryRecvError::Empty) => {
+					ui.ctx().request_repaint_after(Duration::from_millis(100));
+				}
+				Err(TryRecvError::Closed) => self.doctor.pending = None,
+			}
+		}
+
+		self.recheck_first_event(ui);
+
+		CollapsingHeader::new("Setup check")
+			.id_source("doctor")
+			.open(self.doctor.open.then_some(true))
+			.show(ui, |ui| {
+				let running = self.doctor.pending.is_some();
+				let text = match running {
+					true => "Checking...",
+					false => "Run checks",
+				};
+
+				if ui
+					.add_enabled(!running, Button::new(text).fill(colors::SURFACE0))
+					.clicked()
+				{
+					self.run_doctor();
+				}
+
+				if self.doctor.checks.is_empty() {
+					return;
+				}
+
+				Self::spacing(ui);
+
+				Grid::new("doctor-checks")
+					.num_columns(3)
+					.spacing([Self::DEFAULT_SPACING * 2.0, Self::DEFAULT_SPACING])
+					.show(ui, |ui| {
+						for check in &self.doctor.checks {
+							ui.label(outcome_text(check.outcome));
+							ui.label(check.name);
+
+							let details = RichText::new(&check.details).color(colors::SUBTEXT0);
+							let details = ui.label(details);
+
+							if let Some(fix) = &check.fix {
+								details.on_hover_text(fix.as_str());
+								ui.end_row();
+								ui.label("");
+								ui.label("");
+								ui.label(RichText::new(fix).color(colors::LAVENDER));
+							}
+
+							ui.end_row();
+						}
+					});
+			});
+
+		// Only force it open once, after that the user decides.
+		self.doctor.open = false;
+	}
+
+	/// Keeps the first event check up to date while the server is waiting for the game, so users
+	/// don't have to click "Run checks" until it passes.
+	fn recheck_first_event(&mut self, ui: &mut Ui) {
+		let Some(tracker) = &self.tracker else {
+			return;
+		};
+
+		if self.doctor.pending.is_some() {
+			return;
+		}
+
+		let Some(check) = self
+			.doctor
+			.checks
+			.iter_mut()
+			.find(|check| check.name == doctor::FIRST_EVENT && check.outcome == Outcome::Pending)
+		else {
+			return;
+		};
+
+		let game = tokio::task::block_in_place(|| self.config.blocking_lock().game);
+
+		*check = tokio::task::block_in_place(|| {
+			doctor::first_event(game, Some(&tracker.blocking_lock()))
+		});
+
+		ui.ctx().request_repaint_after(FIRST_EVENT_INTERVAL);
+	}
+}
+
+fn outcome_text(outcome: Outcome) -> RichText {
+	match outcome {
+		Outcome::Passed => RichText::new("✔").color(colors::GREEN),
+		Outcome::Warning => RichText::new("⚠").color(colors::YELLOW),
+		Outcome::Failed => RichText::new("✖").color(colors::RED),
+		Outcome::Pending => RichText::new("…").color(colors::OVERLAY1),
+	}
+}
@@ -0,0 +1,309 @@
+//! The "Overlay" tab: build an overlay by clicking around instead of writing CSS.
+
+use {
+	super::Client,
+	crate::{
+		colors,
+		config::OverlayStyle,
+		gsi::State,
+		records::Records,
+		server::{themes, PORT},
+		template::{self, Context},
+	},
+	eframe::{
+		egui::{Button, ComboBox, Frame, RichText, Slider, TextEdit, Ui},
+		epaint::{FontFamily, FontId},
+	},
+	gokz_rs::{Mode, Tier},
+	tokio::sync::broadcast::{error::TryRecvError, Receiver},
+	tracing::{error, info},
+};
+
+/// Latest state and records, so the preview shows what's actually happening in-game.
+pub struct Preview {
+	pub state_receiver: Receiver<State>,
+	pub records_receiver: Receiver<Records>,
+	pub state: Option<State>,
+	pub records: Records,
+}
+
+impl Preview {
+	pub fn new(state_receiver: Receiver<State>, records_receiver: Receiver<Records>) -> Self {
+		Self {
+			state_receiver,
+			records_receiver,
+			state: None,
+			records: Records::default(),
+		}
+	}
+
+	fn update(&mut self) {
+		loop {
+			match self.state_receiver.try_recv() {
+				Ok(state) => self.state = Some(state),
+				Err(TryRecvError::Lagged(_)) => continue,
+				Err(_) => break,
+			}
+		}
+
+		loop {
+			match self.records_receiver.try_recv() {
+				Ok(records) => self.records = records,
+				Err(TryRecvError::Lagged(_)) => continue,
+				Err(_) => break,
+			}
+		}
+	}
+}
+
+impl Client {
+	/// Pixels per `em` in the preview.
+	const PREVIEW_EM: f32 = 10.0;
+
+	pub fn render_overlay_editor(&mut self, ui: &mut Ui) {
+		if let Some(preview) = &mut self.preview {
+			preview.update();
+		}
+
+		let mut style = {
+			let config = tokio::task::block_in_place(|| self.config.blocking_lock());
+			config.overlay_style.clone()
+		};
+
+		ui.columns(2, |columns| {
+			Self::render_style_controls(&mut style, &mut columns[0]);
+			self.render_preview(&style, &mut columns[1]);
+		});
+
+		Self::spacing(ui);
+		ui.separator();
+		Self::spacing(ui);
+
+		ui.horizontal(|ui| {
+			if ui
+				.add(Button::new("Copy URL").fill(colors::SURFACE2))
+				.clicked()
+			{
+				let url = format!(
+					"http://localhost:{PORT}/overlay/compact?fields={}",
+					style.fields().join(",")
+				);
+
+				ui.output_mut(|output| output.copied_text = url);
+				self.notifications
+					.info("Copied URL. Save a theme to keep colors and fonts.")
+					.set_duration(Self::NOTIFICATION_DURATION);
+			}
+
+			if ui
+				.add(Button::new("Save theme").fill(colors::SURFACE2))
+				.clicked()
+			{
+				self.save_theme(&style, ui);
+			}
+		});
+
+		let config = &mut *tokio::task::block_in_place(|| self.config.blocking_lock());
+
+		if config.overlay_style != style {
+			config.overlay_style = style;
+		}
+	}
+
+	fn render_style_controls(style: &mut OverlayStyle, ui: &mut Ui) {
+		ui.horizontal(|ui| {
+			ui.label("Name: ");
+			ui.text_edit_singleline(&mut style.name);
+		});
+
+		Self::spacing(ui);
+
+		ui.horizontal_wrapped(|ui| {
+			ui.checkbox(&mut style.show_map, "Map");
+			ui.checkbox(&mut style.show_tp, "TP");
+			ui.checkbox(&mut style.show_pro, "PRO");
+			ui.checkbox(&mut style.show_pb, "PBs");
+			ui.checkbox(&mut style.show_events, "New records");
+		});
+
+		Self::spacing(ui);
+
+		for (label, color) in [
+			("Text", &mut style.text_color),
+			("TP", &mut style.tp_color),
+			("PRO", &mut style.pro_color),
+			("PB", &mut style.pb_color),
+		] {
+			ui.horizontal(|ui| {
+				ui.label(format!("{label} color: "));
+				Self::render_color_picker(label, color, ui);
+			});
+		}
+
+		Self::spacing(ui);
+
+		ui.horizontal(|ui| {
+			ui.label("Font: ");
+			ComboBox::from_id_source("overlay-font")
+				.selected_text(style.font.as_str())
+				.show_ui(ui, |ui| {
+					for font in OverlayStyle::FONTS {
+						ui.selectable_value(&mut style.font, String::from(font), font);
+					}
+				});
+		});
+
+		ui.add(Slider::new(&mut style.map_size, 1.0..=8.0).text("Map size"));
+		ui.add(Slider::new(&mut style.records_size, 1.0..=8.0).text("Records size"));
+
+		Self::spacing(ui);
+
+		for (label, template) in [
+			("Map", &mut style.map_template),
+			("TP", &mut style.tp_template),
+			("PRO", &mut style.pro_template),
+		] {
+			ui.label(format!("{label} template: "));
+			TextEdit::singleline(template).code_editor().show(ui);
+		}
+	}
+
+	fn render_color_picker(id: &str, selected: &mut String, ui: &mut Ui) {
+		let current = colors::by_name(selected).unwrap_or(colors::TEXT);
+
+		ComboBox::from_id_source(("overlay-color", id))
+			.selected_text(RichText::new(selected.as_str()).color(current))
+			.show_ui(ui, |ui| {
+				for (name, color) in colors::PALETTE {
+					let text = RichText::new(name).color(color);
+					ui.selectable_value(selected, String::from(name), text);
+				}
+			});
+	}
+
+	fn render_preview(&self, style: &OverlayStyle, ui: &mut Ui) {
+		let sample_state = State {
+			player_name: Some(String::from("AlphaKeks")),
+			steam_id: None,
+			map_name: Some(String::from("kz_lionharder")),
+			map_tier: Some(Tier::Hard),
+			mode: Some(Mode::SimpleKZ),
+		};
+
+		let no_records = Records::default();
+
+		let (state, records) = match &self.preview {
+			Some(Preview {
+				state: Some(state),
+				records,
+				..
+			}) => (state, records),
+			Some(Preview {
+				state: None,
+				records,
+				..
+			}) => (&sample_state, records),
+			None => (&sample_state, &no_records),
+		};
+
+		let context = Context { state, records };
+
+		let family = match style.font.as_str() {
+			"Fira Code" => FontFamily::Monospace,
+			_ => FontFamily::Proportional,
+		};
+
+		let color = |name: &str| colors::by_name(name).unwrap_or(colors::TEXT);
+		let map_font = FontId::new(style.map_size * Self::PREVIEW_EM, family.clone());
+		let records_font = FontId::new(style.records_size * Self::PREVIEW_EM, family);
+
+		Frame::none()
+			.fill(colors::CRUST)
+			.inner_margin(Self::DEFAULT_SPACING)
+			.show(ui, |ui| {
+				ui.set_min_width(ui.available_width());
+
+				if style.show_map {
+					let map = template::render(&style.map_template, context);
+					ui.label(
+						RichText::new(map)
+							.font(map_font.clone())
+							.color(color(&style.text_color)),
+					);
+				}
+
+				for (show, label, template, diff, run_color) in [
+					(
+						style.show_tp,
+						"TP",
+						&style.tp_template,
+						"{tp_pb_diff}",
+						&style.tp_color,
+					),
+					(
+						style.show_pro,
+						"PRO",
+						&style.pro_template,
+						"{pro_pb_diff}",
+						&style.pro_color,
+					),
+				] {
+					if !show {
+						continue;
+					}
+
+					ui.horizontal_wrapped(|ui| {
+						let text = |text: String| RichText::new(text).font(records_font.clone());
+
+						ui.label(text(format!("{label} »")).color(color(run_color)));
+						ui.label(
+							text(template::render(template, context))
+								.color(color(&style.text_color)),
+						);
+
+						let diff = template::render(diff, context);
+
+						if style.show_pb && !diff.is_empty() {
+							ui.label(text(format!("(+{diff})")).color(color(&style.pb_color)));
+						}
+					});
+				}
+			});
+
+		if self
+			.preview
+			.as_ref()
+			.is_none_or(|preview| preview.state.is_none())
+		{
+			ui.small("Showing example data until CS:GO sends something.");
+		}
+	}
+
+	fn save_theme(&mut self, style: &OverlayStyle, ui: &mut Ui) {
+		match themes::save(style) {
+			Ok(path) => {
+				info!("Saved theme to `{}`.", path.display());
+
+				{
+					let config = &mut *tokio::task::block_in_place(|| self.config.blocking_lock());
+					config
+						.overlay_templates
+						.insert(style.name.clone(), style.templates());
+				}
+
+				let url = format!("http://localhost:{PORT}/themes/{}/", style.name);
+				ui.output_mut(|output| output.copied_text = url.clone());
+
+				self.notifications
+					.success(format!("Saved theme and copied `{url}`."))
+					.set_duration(Self::NOTIFICATION_DURATION);
+			}
+			Err(why) => {
+				error!("Failed to save theme: {why:?}");
+				self.notifications
+					.error(format!("{why}"))
+					.set_duration(Self::NOTIFICATION_DURATION);
+			}
+		}
+	}
+}
@@ -0,0 +1,700 @@
+//! The "Logs" tab.
+
+use {
+	super::Client,
+	crate::{
+		colors,
+		config::Config,
+		diagnose,
+		log_export::{self, Format, Options},
+		logger::{Log, LogReceiver},
+		redact::Redactor,
+	},
+	chrono::{DateTime, Local, NaiveDateTime, Utc},
+	color_eyre::{eyre::Context, Result},
+	eframe::egui::{
+		Align, Button, ComboBox, Grid, Label, Layout, RichText, ScrollArea, Sense, TextEdit, Ui,
+		Window,
+	},
+	egui_extras::{Column, TableBuilder},
+	regex::{Regex, RegexBuilder},
+	rfd::FileDialog,
+	std::path::Path,
+	tokio::runtime::Handle,
+	tracing::{error, info, Level},
+};
+
+/// What the Logs tab currently shows.
+pub struct LogFilter {
+	/// Which levels are visible.
+	levels: [(Level, bool); 5],
+	/// A module like `gsi`, see [`LogFilter::TARGETS`].
+	target: Option<&'static str>,
+	search: String,
+	regex: bool,
+	/// `search` compiled, if `regex` is enabled. Only recompiled when `search` changes.
+	compiled: Option<(String, Result<Regex, regex::Error>)>,
+	/// Show new logs and stick to the bottom.
+	follow: bool,
+	/// Shown in the detail window.
+	selected: Option<Log>,
+	/// Numbers (see [`LogReceiver::get`]) of the matching logs, oldest first.
+	matching: Vec<u64>,
+	/// Logs before this number have been checked against the filter already.
+	checked: u64,
+	/// The filter `matching` was built with.
+	built_with: Option<FilterKey>,
+	/// While paused, only logs received before this number are shown.
+	paused_at: Option<u64>,
+}
+
+/// Everything that decides whether a log matches; changing any of it rebuilds the view.
+type FilterKey = ([(Level, bool); 5], Option<&'static str>, String, bool);
+
+impl Default for LogFilter {
+	fn default() -> Self {
+		Self {
+			levels: [
+				(Level::TRACE, false),
+				(Level::DEBUG, true),
+				(Level::INFO, true),
+				(Level::WARN, true),
+				(Level::ERROR, true),
+			],
+			target: None,
+			search: String::new(),
+			regex: false,
+			compiled: None,
+			follow: true,
+			selected: None,
+			matching: Vec::new(),
+			checked: 0,
+			built_with: None,
+			paused_at: None,
+		}
+	}
+}
+
+impl LogFilter {
+	pub const TARGETS: [&str; 4] = ["gsi", "server", "gui", "records"];
+
+	fn compile(&mut self) {
+		if !self.regex || self.search.is_empty() {
+			self.compiled = None;
+			return;
+		}
+
+		if matches!(&self.compiled, Some((search, _)) if *search == self.search) {
+			return;
+		}
+
+		let regex = RegexBuilder::new(&self.search)
+			.case_insensitive(true)
+			.build();
+
+		self.compiled = Some((self.search.clone(), regex));
+	}
+
+	fn key(&self) -> FilterKey {
+		(self.levels, self.target, self.search.clone(), self.regex)
+	}
+
+	/// Brings [`LogFilter::matching`] up to date. Only new logs get checked, unless the filter
+	/// changed.
+	fn refresh(&mut self, logger: &LogReceiver) {
+		self.compile();
+
+		let key = self.key();
+
+		if self.built_with.as_ref() != Some(&key) {
+			self.built_with = Some(key);
+			self.matching.clear();
+			self.checked = 0;
+		}
+
+		for number in self.checked.max(logger.first())..logger.received() {
+			if logger.get(number).is_some_and(|log| self.matches(log)) {
+				self.matching.push(number);
+			}
+		}
+
+		self.checked = logger.received();
+
+		// Forget about logs the receiver dropped already.
+		let dropped = self
+			.matching
+			.partition_point(|&number| number < logger.first());
+
+		self.matching.drain(..dropped);
+
+		match self.follow {
+			true => self.paused_at = None,
+			false => {
+				self.paused_at.get_or_insert(logger.received());
+			}
+		}
+	}
+
+	/// The logs to display; everything received after pausing is hidden until unpausing.
+	fn visible(&self) -> &[u64] {
+		let end = match self.paused_at {
+			None => self.matching.len(),
+			Some(paused_at) => self
+				.matching
+				.partition_point(|&number| number < paused_at),
+		};
+
+		&self.matching[..end]
+	}
+
+	fn matches(&self, log: &Log) -> bool {
+		let level_visible = self
+			.levels
+			.iter()
+			.any(|&(level, visible)| visible && level == log.level);
+
+		if !level_visible {
+			return false;
+		}
+
+		if let Some(target) = self.target {
+			let log_target = short_target(&log.target);
+
+			if log_target != target && !log_target.starts_with(&format!("{target}::")) {
+				return false;
+			}
+		}
+
+		if self.search.is_empty() {
+			return true;
+		}
+
+		match &self.compiled {
+			Some((_, Ok(regex))) => regex.is_match(&log.message),
+			// Invalid regex; show everything so the user sees something happening.
+			Some((_, Err(_))) => true,
+			None => log
+				.message
+				.to_lowercase()
+				.contains(&self.search.to_lowercase()),
+		}
+	}
+}
+
+/// The "Export logs" window.
+pub struct LogExport {
+	open: bool,
+	format: Format,
+	redact: bool,
+	range: bool,
+	/// Local time, see [`LogExport::TIME_FORMAT`].
+	since: String,
+	until: String,
+}
+
+impl Default for LogExport {
+	fn default() -> Self {
+		Self {
+			open: false,
+			format: Format::Text,
+			redact: true,
+			range: false,
+			since: String::new(),
+			until: String::new(),
+		}
+	}
+}
+
+impl LogExport {
+	const TIME_FORMAT: &str = "%Y/%m/%d %H:%M:%S";
+	const TIME_HINT: &str = "YYYY/MM/DD hh:mm:ss";
+}
+
+impl Client {
+	/// Every row is a single line; click a row to see everything.
+	const LOG_ROW_HEIGHT: f32 = Self::DEFAULT_SPACING * 4.0;
+
+	pub fn render_logs(&mut self, ui: &mut Ui) {
+		if self.logger.is_none() {
+			ui.vertical_centered(|ui| ui.colored_label(colors::RED, "Logs are displayed on STDOUT."));
+			return;
+		}
+
+		let button = {
+			let mut button = None;
+
+			ui.horizontal(|ui| {
+				self.render_export_button(ui);
+				self.open_log_folder(ui);
+				self.render_diagnose_button(ui);
+				let jump_button = Button::new("Go to bottom").fill(colors::SURFACE0);
+				button = Some(ui.add(jump_button));
+				ui.checkbox(&mut self.log_filter.follow, "Follow")
+					.on_hover_text("Uncheck to pause the log view.");
+			});
+
+			Self::render_log_filter(&mut self.log_filter, ui);
+			self.render_log_level(ui);
+
+			ui.add_space(Self::DEFAULT_SPACING);
+			ui.separator();
+			ui.add_space(Self::DEFAULT_SPACING);
+
+			button.unwrap()
+		};
+
+		let Some(logger) = &self.logger else {
+			return;
+		};
+
+		let filter = &mut self.log_filter;
+		filter.refresh(logger);
+
+		let logs = filter.visible();
+
+		let mut table = TableBuilder::new(ui)
+			.striped(true)
+			.resizable(false)
+			.stick_to_bottom(filter.follow)
+			.cell_layout(Layout::left_to_right(Align::Center))
+			.column(Column::auto())
+			.column(Column::auto())
+			.column(Column::auto())
+			.column(Column::remainder())
+			.vscroll(true)
+			.min_scrolled_height(0.0);
+
+		if button.clicked() {
+			table = table.scroll_to_row(logs.len(), None);
+		}
+
+		let mut selected = None;
+
+		// Only the visible rows get laid out.
+		table.body(|body| {
+			body.rows(Self::LOG_ROW_HEIGHT, logs.len(), |idx, mut row| {
+				let Some(log) = logs.get(idx).and_then(|&number| logger.get(number)) else {
+					return;
+				};
+
+				row.col(|ui| {
+					ui.label(timestamp_text(log));
+				});
+
+				row.col(|ui| {
+					ui.label(level_text(log.level));
+				});
+
+				row.col(|ui| {
+					ui.label(RichText::new(short_target(&log.target)).color(colors::OVERLAY1));
+				});
+
+				row.col(|ui| {
+					let first_line = log.message.lines().next().unwrap_or_default();
+					let text = RichText::new(first_line).color(colors::LAVENDER).monospace();
+
+					if ui
+						.add(Label::new(text).sense(Sense::click()))
+						.on_hover_text(location(log))
+						.clicked()
+					{
+						selected = Some(log.clone());
+					}
+				});
+			});
+		});
+
+		if selected.is_some() {
+			filter.selected = selected;
+		}
+
+		Self::render_log_details(&mut filter.selected, ui);
+		self.render_log_export(ui);
+	}
+
+	fn render_log_filter(filter: &mut LogFilter, ui: &mut Ui) {
+		ui.horizontal_wrapped(|ui| {
+			for (level, visible) in &mut filter.levels {
+				ui.checkbox(visible, level_text(*level));
+			}
+
+			ComboBox::from_id_source("log-target")
+				.selected_text(filter.target.unwrap_or("all modules"))
+				.show_ui(ui, |ui| {
+					ui.selectable_value(&mut filter.target, None, "all modules");
+
+					for target in LogFilter::TARGETS {
+						ui.selectable_value(&mut filter.target, Some(target), target);
+					}
+				});
+		});
+
+		ui.horizontal(|ui| {
+			TextEdit::singleline(&mut filter.search)
+				.hint_text("Search")
+				.show(ui);
+
+			ui.checkbox(&mut filter.regex, "Regex");
+
+			if let Some((_, Err(why))) = &filter.compiled {
+				ui.colored_label(colors::RED, "Invalid regex")
+					.on_hover_text(why.to_string());
+			}
+		});
+	}
+
+	fn render_log_level(&mut self, ui: &mut Ui) {
+		ui.horizontal(|ui| {
+			ui.label("Log level: ");
+
+			ComboBox::from_id_source("log-level")
+				.selected_text(self.log_level_prompt.as_str())
+				.show_ui(ui, |ui| {
+					for level in ["trace", "debug", "info", "warn", "error"] {
+						ui.selectable_value(&mut self.log_level_prompt, String::from(level), level);
+					}
+				});
+
+			TextEdit::singleline(&mut self.log_level_prompt)
+				.hint_text("info,schnose_gsi_client::gsi=trace")
+				.code_editor()
+				.show(ui);
+
+			let changed = self.log_level_prompt != self.log_level.current();
+
+			if ui
+				.add_enabled(changed, Button::new("Apply").fill(colors::SURFACE0))
+				.clicked()
+			{
+				match self.log_level.set(&self.log_level_prompt) {
+					Ok(()) => {
+						let config = &mut *tokio::task::block_in_place(|| self.config.blocking_lock());
+						config.log_level = self.log_level_prompt.clone();
+					}
+					Err(why) => {
+						self.notifications
+							.error(format!("{why}"))
+							.set_duration(Self::NOTIFICATION_DURATION);
+					}
+				}
+			}
+		});
+	}
+
+	fn render_log_details(selected: &mut Option<Log>, ui: &mut Ui) {
+		let Some(log) = selected.as_ref() else {
+			return;
+		};
+
+		let mut open = true;
+
+		Window::new("Log details")
+			.open(&mut open)
+			.resizable(true)
+			.show(ui.ctx(), |ui| {
+				ScrollArea::vertical().show(ui, |ui| {
+					ui.horizontal(|ui| {
+						ui.label(timestamp_text(log));
+						ui.label(level_text(log.level));
+					});
+
+					ui.label(RichText::new(&log.target).color(colors::OVERLAY1));
+					ui.label(RichText::new(location(log)).color(colors::OVERLAY1));
+
+					ui.separator();
+					ui.label(RichText::new(&log.message).color(colors::LAVENDER).monospace());
+
+					if !log.fields.is_empty() {
+						ui.separator();
+						ui.label("Fields");
+						ui.label(RichText::new(pretty(&log.fields)).monospace());
+					}
+
+					if !log.spans.is_empty() {
+						ui.separator();
+						ui.label("Spans");
+						ui.label(RichText::new(pretty(&log.spans)).monospace());
+					}
+				});
+			});
+
+		if !open {
+			*selected = None;
+		}
+	}
+
+	fn render_diagnose_button(&mut self, ui: &mut Ui) {
+		let text = RichText::new("Create diagnostics").color(colors::TEXT);
+		let button = Button::new(text).fill(colors::SURFACE0);
+
+		if !ui
+			.add(button)
+			.on_hover_text("Collects config, logs and GSI setup into a zip file for bug reports.")
+			.clicked()
+		{
+			return;
+		}
+
+		let Some(path) = FileDialog::new()
+			.set_file_name(&diagnose::default_file_name())
+			.add_filter("zip", &["zip"])
+			.save_file()
+		else {
+			return;
+		};
+
+		let config = tokio::task::block_in_place(|| self.config.blocking_lock()).clone();
+
+		let logs = self.logger.as_ref().map(|logger| {
+			let options = Options {
+				format: Format::JsonLines,
+				since: None,
+				until: None,
+				// The whole bundle gets redacted.
+				redactor: None,
+			};
+
+			log_export::export(logger.logs(), &options)
+		});
+
+		let result = tokio::task::block_in_place(|| {
+			Handle::current().block_on(diagnose::create(&config, logs, &path))
+		});
+
+		match result {
+			Ok(()) => {
+				self.notifications
+					.success("Created diagnostics.")
+					.set_duration(Self::NOTIFICATION_DURATION);
+			}
+			Err(why) => {
+				error!("Failed to create diagnostics: {why:?}");
+				self.notifications
+					.error("Failed to create diagnostics.")
+					.set_duration(Self::NOTIFICATION_DURATION);
+			}
+		}
+	}
+
+	fn open_log_folder(&mut self, ui: &mut Ui) {
+		let enabled = tokio::task::block_in_place(|| self.config.blocking_lock())
+			.log_files
+			.enabled;
+
+		let text = RichText::new("Open log folder").color(colors::TEXT);
+		let button = Button::new(text).fill(colors::SURFACE0);
+
+		if !ui
+			.add_enabled(enabled, button)
+			.on_disabled_hover_text("Log files are disabled in the config.")
+			.clicked()
+		{
+			return;
+		}
+
+		let result = Config::logs_dir().and_then(|logs_dir| open_folder(&logs_dir));
+
+		if let Err(why) = result {
+			error!("Failed to open log folder: {why:?}");
+			self.notifications
+				.error("Failed to open log folder.")
+				.set_duration(Self::NOTIFICATION_DURATION);
+		}
+	}
+
+	fn render_export_button(&mut self, ui: &mut Ui) {
+		let text = RichText::new("Export logs").color(colors::TEXT);
+		let button = Button::new(text).fill(colors::SURFACE0);
+
+		if !ui.add(button).clicked() {
+			return;
+		}
+
+		// Default to everything that's currently loaded.
+		let logs = self.logger.as_ref().map(|logger| logger.logs());
+		let since = logs.and_then(|logs| logs.front()).map_or_else(Utc::now, |log| log.timestamp);
+		let until = logs.and_then(|logs| logs.back()).map_or_else(Utc::now, |log| log.timestamp);
+
+		self.log_export.since = input_time(since);
+		self.log_export.until = input_time(until);
+		self.log_export.open = true;
+	}
+
+	fn render_log_export(&mut self, ui: &mut Ui) {
+		let mut open = self.log_export.open;
+		let mut export = false;
+
+		Window::new("Export logs")
+			.open(&mut open)
+			.resizable(false)
+			.show(ui.ctx(), |ui| {
+				let options = &mut self.log_export;
+
+				ComboBox::from_id_source("log-export-format")
+					.selected_text(options.format.name())
+					.show_ui(ui, |ui| {
+						for format in Format::ALL {
+							ui.selectable_value(&mut options.format, format, format.name());
+						}
+					});
+
+				ui.checkbox(&mut options.redact, "Redact secrets")
+					.on_hover_text("Masks the API key, GSI auth tokens and SteamIDs.");
+
+				ui.checkbox(&mut options.range, "Only export a time range");
+
+				ui.add_enabled_ui(options.range, |ui| {
+					Grid::new("log-export-range").show(ui, |ui| {
+						ui.label("From");
+						TextEdit::singleline(&mut options.since)
+							.hint_text(LogExport::TIME_HINT)
+							.show(ui);
+						ui.end_row();
+
+						ui.label("To");
+						TextEdit::singleline(&mut options.until)
+							.hint_text(LogExport::TIME_HINT)
+							.show(ui);
+						ui.end_row();
+					});
+				});
+
+				ui.add_space(Self::DEFAULT_SPACING);
+
+				export = ui
+					.add(Button::new("Export").fill(colors::SURFACE0))
+					.clicked();
+			});
+
+		self.log_export.open = open;
+
+		if export {
+			self.export_logs();
+		}
+	}
+
+	fn export_logs(&mut self) {
+		let (since, until) = match self.log_export.range {
+			false => (None, None),
+			true => match (
+				parse_time(&self.log_export.since),
+				parse_time(&self.log_export.until),
+			) {
+				(Some(since), Some(until)) => (Some(since), Some(until)),
+				_ => {
+					self.notifications
+						.error(format!("Times must look like `{}`.", LogExport::TIME_HINT))
+						.set_duration(Self::NOTIFICATION_DURATION);
+					return;
+				}
+			},
+		};
+
+		let redactor = self.log_export.redact.then(|| {
+			Redactor::new(&tokio::task::block_in_place(|| self.config.blocking_lock()))
+		});
+
+		let options = Options {
+			format: self.log_export.format,
+			since,
+			until,
+			redactor,
+		};
+
+		let timestamp = Utc::now().format("%Y%m%d%H%M%S");
+		let extension = options.format.extension();
+		let file_name = format!("{timestamp}-schnose-gsi-client.{extension}");
+
+		let Some(log_path) = FileDialog::new()
+			.set_file_name(&file_name)
+			.add_filter(options.format.name(), &[extension])
+			.save_file()
+		else {
+			return;
+		};
+
+		let Some(logger) = &self.logger else {
+			return error!("This UI should only be rendered if a logger is present.");
+		};
+
+		let logs = log_export::export(logger.logs(), &options);
+
+		match std::fs::write(&log_path, logs) {
+			Ok(()) => {
+				info!("Wrote logs to `{}`.", log_path.display());
+				self.log_export.open = false;
+			}
+			Err(why) => error!("Failed to write logs to `{}`: {why:#?}", log_path.display()),
+		}
+	}
+}
+
+/// Opens `path` in the system's file manager.
+fn open_folder(path: &Path) -> Result<()> {
+	let program = match std::env::consts::OS {
+		"windows" => "explorer",
+		"macos" => "open",
+		_ => "xdg-open",
+	};
+
+	std::process::Command::new(program)
+		.arg(path)
+		.spawn()
+		.with_context(|| format!("Failed to run `{program}`."))?;
+
+	Ok(())
+}
+
+fn input_time(timestamp: DateTime<Utc>) -> String {
+	timestamp
+		.with_timezone(&Local)
+		.format(LogExport::TIME_FORMAT)
+		.to_string()
+}
+
+fn parse_time(input: &str) -> Option<DateTime<Utc>> {
+	NaiveDateTime::parse_from_str(input.trim(), LogExport::TIME_FORMAT)
+		.ok()?
+		.and_local_timezone(Local)
+		.earliest()
+		.map(|timestamp| timestamp.with_timezone(&Utc))
+}
+
+fn timestamp_text(log: &Log) -> RichText {
+	let timestamp = log.timestamp.with_timezone(&Local);
+
+	RichText::new(timestamp.format("%Y/%m/%d %H:%M:%S").to_string())
+		.color(colors::RED)
+		.monospace()
+}
+
+fn level_text(level: Level) -> RichText {
+	match level {
+		Level::TRACE => RichText::new("[TRACE]").color(colors::TEAL),
+		Level::DEBUG => RichText::new("[DEBUG]").color(colors::BLUE),
+		Level::INFO => RichText::new("[INFO] ").color(colors::GREEN),
+		Level::WARN => RichText::new("[WARN] ").color(colors::YELLOW),
+		_ => RichText::new("[ERROR]").color(colors::RED),
+	}
+	.monospace()
+}
+
+/// `schnose_gsi_client::gsi` => `gsi`
+fn short_target(target: &str) -> &str {
+	target
+		.strip_prefix("schnose_gsi_client::")
+		.unwrap_or(target)
+}
+
+fn pretty(value: &impl serde::Serialize) -> String {
+	serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+/// `src/gsi/mod.rs:42`
+fn location(log: &Log) -> String {
+	match (&log.file, log.line) {
+		(Some(file), Some(line)) => format!("{file}:{line}"),
+		(Some(file), None) => file.clone(),
+		_ => String::from("unknown location"),
+	}
+}
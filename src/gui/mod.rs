@@ -1,26 +1,44 @@
 use {
 	crate::{colors, config::Config},
 	eframe::egui::{CentralPanel, RichText, TopBottomPanel},
-	std::fs::File,
+	std::{fs::File, time::Duration},
 	tracing::info,
 };
 
 mod client;
 pub use client::Client;
 
+mod doctor;
+mod editor;
+mod logs;
+mod session;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Tab {
 	Main,
+	Overlay,
+	Session,
 	Logs,
 }
 
+/// How often logs get collected while nothing else triggers a repaint.
+const LOG_INTERVAL: Duration = Duration::from_secs(1);
+
 impl eframe::App for Client {
 	fn update(&mut self, ctx: &eframe::egui::Context, _: &mut eframe::Frame) {
+		// Logs pile up in the channel until they're collected, no matter which tab is open.
+		if let Some(logger) = &mut self.logger {
+			logger.update();
+			ctx.request_repaint_after(LOG_INTERVAL);
+		}
+
 		TopBottomPanel::top("header-panel").show(ctx, |ui| {
 			ui.add_space(Self::DEFAULT_SPACING);
 
 			ui.horizontal(|ui| {
 				ui.selectable_value(&mut self.current_tab, Tab::Main, "Main");
+				ui.selectable_value(&mut self.current_tab, Tab::Overlay, "Overlay");
+				ui.selectable_value(&mut self.current_tab, Tab::Session, "Session");
 				ui.selectable_value(&mut self.current_tab, Tab::Logs, "Logs");
 			});
 
@@ -40,6 +58,8 @@ impl eframe::App for Client {
 		CentralPanel::default().show(ctx, |ui| {
 			match self.current_tab {
 				Tab::Main => self.render_main(ui),
+				Tab::Overlay => self.render_overlay_editor(ui),
+				Tab::Session => self.render_session(ui),
 				Tab::Logs => self.render_logs(ui),
 			};
 		});
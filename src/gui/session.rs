@@ -0,0 +1,127 @@
+//! The "Session" tab.
+
+use {
+	super::Client,
+	crate::colors,
+	chrono::{Local, Utc},
+	eframe::egui::{Button, Grid, RichText, ScrollArea, Ui},
+	rfd::FileDialog,
+	tracing::{error, info},
+};
+
+impl Client {
+	pub fn render_session(&mut self, ui: &mut Ui) {
+		let stats = tokio::task::block_in_place(|| self.session.blocking_lock().stats());
+
+		ui.horizontal(|ui| {
+			if ui
+				.add(Button::new("Reset").fill(colors::SURFACE0))
+				.clicked()
+			{
+				tokio::task::block_in_place(|| self.session.blocking_lock().reset());
+				info!("Started a new session.");
+			}
+
+			if ui
+				.add(Button::new("Export CSV").fill(colors::SURFACE0))
+				.clicked()
+			{
+				let csv = tokio::task::block_in_place(|| self.session.blocking_lock().to_csv());
+				self.export_session("csv", csv);
+			}
+
+			if ui
+				.add(Button::new("Export JSON").fill(colors::SURFACE0))
+				.clicked()
+			{
+				match serde_json::to_string_pretty(&stats) {
+					Ok(json) => self.export_session("json", json),
+					Err(why) => error!("Failed to serialize session: {why:?}"),
+				}
+			}
+		});
+
+		Self::spacing(ui);
+		ui.separator();
+		Self::spacing(ui);
+
+		Grid::new("session-stats")
+			.num_columns(2)
+			.spacing([Self::DEFAULT_SPACING * 4.0, Self::DEFAULT_SPACING])
+			.show(ui, |ui| {
+				let started_at = stats.started_at.with_timezone(&Local);
+				let stat = |ui: &mut Ui, label: &str, value: String| {
+					ui.label(label);
+					ui.label(RichText::new(value).color(colors::LAVENDER));
+					ui.end_row();
+				};
+
+				stat(ui, "Started", started_at.format("%H:%M").to_string());
+				stat(ui, "Maps played", stats.maps_played.to_string());
+				stat(ui, "Current map", stats.current_map.clone().unwrap_or_default());
+				stat(
+					ui,
+					"Time on current map",
+					stats
+						.time_on_current_map
+						.map(format_duration)
+						.unwrap_or_default(),
+				);
+				stat(ui, "Mode switches", stats.mode_switches.to_string());
+				stat(ui, "New PBs", stats.personal_bests.to_string());
+				stat(ui, "New WRs", stats.world_records.to_string());
+			});
+
+		Self::spacing(ui);
+		ui.separator();
+		Self::spacing(ui);
+
+		ScrollArea::vertical().show(ui, |ui| {
+			Grid::new("session-maps")
+				.num_columns(2)
+				.striped(true)
+				.show(ui, |ui| {
+					for (map_name, seconds) in &stats.time_per_map {
+						ui.label(map_name);
+						ui.label(format_duration(*seconds));
+						ui.end_row();
+					}
+				});
+		});
+	}
+
+	fn export_session(&mut self, extension: &str, contents: String) {
+		let timestamp = Utc::now().format("%Y%m%d%H%M%S");
+		let file_name = format!("{timestamp}-session.{extension}");
+
+		let Some(path) = FileDialog::new().set_file_name(&file_name).save_file() else {
+			return;
+		};
+
+		match std::fs::write(&path, contents) {
+			Ok(()) => {
+				info!("Exported session to `{}`.", path.display());
+				self.notifications
+					.success("Exported session.")
+					.set_duration(Self::NOTIFICATION_DURATION);
+			}
+			Err(why) => {
+				error!("Failed to export session to `{}`: {why:#?}", path.display());
+				self.notifications
+					.error(format!("Failed to export session: {why}"))
+					.set_duration(Self::NOTIFICATION_DURATION);
+			}
+		}
+	}
+}
+
+/// 2520.0 => 42m
+fn format_duration(seconds: f64) -> String {
+	let seconds = seconds as u64;
+
+	match (seconds / 3600, (seconds % 3600) / 60) {
+		(0, 0) => format!("{seconds}s"),
+		(0, minutes) => format!("{minutes}m"),
+		(hours, minutes) => format!("{hours}h {minutes:02}m"),
+	}
+}
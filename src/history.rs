@@ -0,0 +1,275 @@
+//! A local history of everything that happened while the client was running, stored as JSON
+//! lines in `history.jsonl` next to the config file.
+//!
+//! Every state transition and every set of records fetched from the GlobalAPI is appended as a
+//! single line, so the file can also be inspected with `jq` or similar tools.
+
+use {
+	crate::{
+		config::Config,
+		gsi::State,
+		records::{Record, Records},
+		template::format_time,
+	},
+	chrono::{DateTime, Duration, Local, Utc},
+	color_eyre::{eyre::Context, Result},
+	gokz_rs::{Mode, SteamID},
+	serde::{Deserialize, Serialize},
+	std::{path::PathBuf, sync::Arc},
+	tokio::{
+		io::AsyncWriteExt,
+		sync::{
+			broadcast::{error::RecvError, Receiver},
+			Mutex,
+		},
+	},
+	tracing::{error, info},
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+	pub at: DateTime<Utc>,
+	#[serde(flatten)]
+	pub kind: EntryKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EntryKind {
+	/// The player, map or mode changed.
+	State(State),
+	/// Records fetched from the GlobalAPI.
+	Records(Snapshot),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+	pub source: Source,
+	pub map_name: String,
+	pub mode: Mode,
+	/// The player PBs were fetched for.
+	pub steam_id: Option<SteamID>,
+	pub records: Records,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+	/// The record watcher.
+	Poll,
+	/// `GET /wrs`
+	Wrs,
+	/// `GET /pbs`
+	Pbs,
+}
+
+impl EntryKind {
+	pub fn map_name(&self) -> Option<&str> {
+		match self {
+			EntryKind::State(state) => state.map_name.as_deref(),
+			EntryKind::Records(snapshot) => Some(&snapshot.map_name),
+		}
+	}
+}
+
+/// Which entries [`History::query`] should return. Everything is optional.
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct Filter {
+	pub since: Option<DateTime<Utc>>,
+	pub until: Option<DateTime<Utc>>,
+	pub map_name: Option<String>,
+	pub kind: Option<Kind>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+	State,
+	Records,
+}
+
+impl Filter {
+	fn matches(&self, entry: &Entry) -> bool {
+		let kind = match entry.kind {
+			EntryKind::State(_) => Kind::State,
+			EntryKind::Records(_) => Kind::Records,
+		};
+
+		self.since.is_none_or(|since| entry.at >= since)
+			&& self.until.is_none_or(|until| entry.at <= until)
+			&& self.kind.is_none_or(|wanted| wanted == kind)
+			&& self
+				.map_name
+				.as_deref()
+				.is_none_or(|map_name| entry.kind.map_name() == Some(map_name))
+	}
+}
+
+#[derive(Debug)]
+pub struct History {
+	path: PathBuf,
+	/// Held while writing, so [`History::prune`] can't drop entries appended while it rewrites
+	/// the file.
+	write_lock: Mutex<()>,
+}
+
+impl History {
+	pub fn open() -> Result<Self> {
+		Ok(Self {
+			path: Config::history_path()?,
+			write_lock: Mutex::new(()),
+		})
+	}
+
+	pub async fn append(&self, kind: EntryKind) -> Result<()> {
+		let entry = Entry {
+			at: Utc::now(),
+			kind,
+		};
+		let mut line = serde_json::to_string(&entry).context("Failed to serialize entry.")?;
+		line.push('\n');
+
+		let _guard = self.write_lock.lock().await;
+
+		// A single write per entry, so concurrent writers can't interleave lines.
+		tokio::fs::OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(&self.path)
+			.await
+			.context("Failed to open history file.")?
+			.write_all(line.as_bytes())
+			.await
+			.context("Failed to write to history file.")
+	}
+
+	/// Every entry matching `filter`, oldest first. Lines that can't be parsed are skipped.
+	pub async fn query(&self, filter: &Filter) -> Result<Vec<Entry>> {
+		if !self.path.exists() {
+			return Ok(Vec::new());
+		}
+
+		let history = tokio::fs::read_to_string(&self.path)
+			.await
+			.context("Failed to read history file.")?;
+
+		Ok(history
+			.lines()
+			.filter_map(|line| serde_json::from_str::<Entry>(line).ok())
+			.filter(|entry| filter.matches(entry))
+			.collect())
+	}
+
+	/// Deletes every entry older than `retention_days`.
+	pub async fn prune(&self, retention_days: u32) -> Result<()> {
+		let filter = Filter {
+			since: Some(Utc::now() - Duration::days(retention_days.into())),
+			..Default::default()
+		};
+
+		let _guard = self.write_lock.lock().await;
+		let entries = self.query(&filter).await?;
+		let mut history = String::new();
+
+		for entry in &entries {
+			history.push_str(&serde_json::to_string(entry).context("Failed to serialize entry.")?);
+			history.push('\n');
+		}
+
+		let temp_path = self.path.with_extension("jsonl.tmp");
+
+		tokio::fs::write(&temp_path, history)
+			.await
+			.context("Failed to write history file.")?;
+
+		tokio::fs::rename(&temp_path, &self.path)
+			.await
+			.context("Failed to replace history file.")?;
+
+		info!("Pruned history, {} entries left.", entries.len());
+
+		Ok(())
+	}
+}
+
+/// Applies the retention setting and records every state transition.
+pub async fn watch(
+	mut state_receiver: Receiver<State>,
+	history: Arc<History>,
+	retention_days: u32,
+) {
+	if retention_days > 0 {
+		if let Err(why) = history.prune(retention_days).await {
+			error!("Failed to prune history: {why:?}");
+		}
+	}
+
+	let mut previous = None;
+
+	loop {
+		let state = match state_receiver.recv().await {
+			Ok(state) => state,
+			Err(RecvError::Lagged(_)) => continue,
+			Err(RecvError::Closed) => break,
+		};
+
+		if previous.as_ref() == Some(&state) {
+			continue;
+		}
+
+		previous = Some(state.clone());
+
+		if let Err(why) = history.append(EntryKind::State(state)).await {
+			error!("Failed to record state: {why:?}");
+		}
+	}
+}
+
+/// `history` subcommand
+pub async fn print(filter: &Filter, json: bool) -> Result<()> {
+	let entries = History::open()?.query(filter).await?;
+
+	for entry in entries {
+		if json {
+			println!(
+				"{}",
+				serde_json::to_string(&entry).context("Failed to serialize entry.")?
+			);
+			continue;
+		}
+
+		let at = entry.at.with_timezone(&Local).format("%Y/%m/%d %H:%M:%S");
+
+		match entry.kind {
+			EntryKind::State(state) => println!(
+				"{at}  {} [{}]",
+				state.map_name.as_deref().unwrap_or("(menu)"),
+				state.mode.map(State::mode_short).unwrap_or("?"),
+			),
+			EntryKind::Records(Snapshot {
+				map_name,
+				mode,
+				records,
+				..
+			}) => {
+				let time = |record: &Option<Record>| {
+					record
+						.as_ref()
+						.map(|record| format_time(record.time))
+						.unwrap_or_else(|| String::from("-"))
+				};
+
+				println!(
+					"{at}  {map_name} [{}]  TP WR {}  PRO WR {}  TP PB {}  PRO PB {}",
+					State::mode_short(mode),
+					time(&records.tp_wr),
+					time(&records.pro_wr),
+					time(&records.tp_pb),
+					time(&records.pro_pb),
+				);
+			}
+		}
+	}
+
+	Ok(())
+}
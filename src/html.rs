@@ -0,0 +1,28 @@
+//! Helpers for building HTML by hand.
+
+/// Makes `text` safe to use as element content or inside a quoted attribute.
+pub fn escape(text: &str) -> String {
+	text.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+		.replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn escapes_markup() {
+		assert_eq!(
+			escape(r#"<script>alert('&"')</script>"#),
+			"&lt;script&gt;alert(&#39;&amp;&quot;&#39;)&lt;/script&gt;"
+		);
+	}
+
+	#[test]
+	fn leaves_text_alone() {
+		assert_eq!(escape("Unknown layout `minimal`."), "Unknown layout `minimal`.");
+	}
+}
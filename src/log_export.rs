@@ -0,0 +1,141 @@
+//! Turns the logs collected by [`crate::logger::LogReceiver`] into files that can be shared.
+
+use {
+	crate::{html::escape, logger::Log, redact::Redactor},
+	chrono::{DateTime, Local, Utc},
+	std::fmt::Write,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+	Text,
+	JsonLines,
+	Html,
+}
+
+impl Format {
+	pub const ALL: [Self; 3] = [Self::Text, Self::JsonLines, Self::Html];
+
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::Text => "Plain text",
+			Self::JsonLines => "JSON lines",
+			Self::Html => "HTML report",
+		}
+	}
+
+	pub const fn extension(self) -> &'static str {
+		match self {
+			Self::Text => "log",
+			Self::JsonLines => "jsonl",
+			Self::Html => "html",
+		}
+	}
+}
+
+/// Which logs to export and how.
+#[derive(Debug, Clone)]
+pub struct Options {
+	pub format: Format,
+	pub since: Option<DateTime<Utc>>,
+	pub until: Option<DateTime<Utc>>,
+	/// Mask secrets and SteamIDs, see [`Redactor`].
+	pub redactor: Option<Redactor>,
+}
+
+impl Options {
+	fn includes(&self, log: &Log) -> bool {
+		self.since.is_none_or(|since| log.timestamp >= since)
+			&& self.until.is_none_or(|until| log.timestamp <= until)
+	}
+
+	fn redact(&self, text: &str) -> String {
+		match &self.redactor {
+			Some(redactor) => redactor.redact(text),
+			None => String::from(text),
+		}
+	}
+}
+
+pub fn export<'a>(logs: impl IntoIterator<Item = &'a Log>, options: &Options) -> String {
+	let logs = logs
+		.into_iter()
+		.filter(|log| options.includes(log));
+
+	match options.format {
+		Format::Text => logs.map(|log| text_line(log, options) + "\n").collect(),
+		Format::JsonLines => logs
+			.map(|log| options.redact(&log.raw) + "\n")
+			.collect(),
+		Format::Html => html_report(logs, options),
+	}
+}
+
+/// `2023/04/01 13:37:00 [INFO] gsi: message {"field":"value"}`
+fn text_line(log: &Log, options: &Options) -> String {
+	let mut line = format!(
+		"{} [{}] {}: {}",
+		timestamp(log),
+		log.level,
+		log.target,
+		options.redact(&log.message)
+	);
+
+	if !log.fields.is_empty() {
+		let fields = serde_json::to_string(&log.fields).unwrap_or_default();
+		line.push(' ');
+		line.push_str(&options.redact(&fields));
+	}
+
+	line
+}
+
+fn html_report<'a>(logs: impl Iterator<Item = &'a Log>, options: &Options) -> String {
+	let mut html = String::from(concat!(
+		"<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n",
+		"<title>schnose-gsi-client logs</title>\n<style>\n",
+		"body { background: #1e1e2e; color: #cdd6f4; font-family: monospace; }\n",
+		"table { border-collapse: collapse; width: 100%; }\n",
+		"td { padding: 2px 8px; vertical-align: top; white-space: pre-wrap; }\n",
+		"tr:nth-child(even) { background: #313244; }\n",
+		".TRACE { color: #94e2d5; } .DEBUG { color: #89b4fa; } .INFO { color: #a6e3a1; }\n",
+		".WARN { color: #f9e2af; } .ERROR { color: #f38ba8; }\n",
+		"</style>\n</head>\n<body>\n",
+	));
+
+	let _ = writeln!(
+		html,
+		"<h1>schnose-gsi-client v{}</h1>\n<p>Exported {}</p>\n<table>",
+		env!("CARGO_PKG_VERSION"),
+		Local::now().format("%Y/%m/%d %H:%M:%S")
+	);
+
+	for log in logs {
+		let mut message = options.redact(&log.message);
+
+		if !log.fields.is_empty() {
+			let fields = serde_json::to_string_pretty(&log.fields).unwrap_or_default();
+			message.push('\n');
+			message.push_str(&options.redact(&fields));
+		}
+
+		let _ = writeln!(
+			html,
+			"<tr><td>{}</td><td class=\"{level}\">{level}</td><td>{}</td><td>{}</td></tr>",
+			timestamp(log),
+			escape(&log.target),
+			escape(&message),
+			level = log.level,
+		);
+	}
+
+	html.push_str("</table>\n</body>\n</html>\n");
+	html
+}
+
+fn timestamp(log: &Log) -> String {
+	log.timestamp
+		.with_timezone(&Local)
+		.format("%Y/%m/%d %H:%M:%S")
+		.to_string()
+}
@@ -0,0 +1,166 @@
+//! Log files in [`Config::logs_dir`], so logs survive a crash.
+//!
+//! Every run starts a new file. Files get rotated once a day or once they reach a certain size,
+//! and only the newest [`LogFilesConfig::keep`] files are kept around.
+
+use {
+	crate::config::{Config, LogFilesConfig, LogFormat, Rotation},
+	chrono::{Local, NaiveDate},
+	color_eyre::Result,
+	std::{
+		fs::File,
+		io::Write,
+		path::PathBuf,
+		sync::{Arc, Mutex},
+	},
+	tracing::Subscriber,
+	tracing_subscriber::{fmt::format::FmtSpan, registry::LookupSpan, Layer},
+};
+
+const FILE_PREFIX: &str = "schnose-gsi-client.";
+
+/// A `tracing` layer writing into rotating log files.
+pub fn layer<S>(config: &LogFilesConfig) -> Result<Box<dyn Layer<S> + Send + Sync>>
+where
+	S: Subscriber + for<'a> LookupSpan<'a>,
+{
+	let writer = Arc::new(RotatingFile::new(config)?);
+
+	let layer = tracing_subscriber::fmt::layer()
+		.with_ansi(false)
+		.with_file(true)
+		.with_line_number(true)
+		.with_span_events(FmtSpan::NEW)
+		.with_writer(writer);
+
+	Ok(match config.format {
+		LogFormat::Json => layer.json().boxed(),
+		LogFormat::Plain => layer.boxed(),
+	})
+}
+
+#[derive(Debug)]
+pub struct RotatingFile {
+	dir: PathBuf,
+	extension: &'static str,
+	rotation: Rotation,
+	max_bytes: u64,
+	keep: usize,
+	current: Mutex<Option<Current>>,
+}
+
+#[derive(Debug)]
+struct Current {
+	file: File,
+	date: NaiveDate,
+	size: u64,
+}
+
+impl RotatingFile {
+	pub fn new(config: &LogFilesConfig) -> Result<Self> {
+		let extension = match config.format {
+			LogFormat::Json => "json",
+			LogFormat::Plain => "log",
+		};
+
+		Ok(Self {
+			dir: Config::logs_dir()?,
+			extension,
+			rotation: config.rotation,
+			max_bytes: config.max_size_mb.max(1) * 1024 * 1024,
+			keep: config.keep.max(1),
+			current: Mutex::new(None),
+		})
+	}
+
+	fn needs_rotation(&self, current: &Current, len: usize) -> bool {
+		match self.rotation {
+			Rotation::Daily => current.date != Local::now().date_naive(),
+			Rotation::Size => current.size + len as u64 > self.max_bytes,
+		}
+	}
+
+	fn open(&self) -> std::io::Result<Current> {
+		let now = Local::now();
+		let file_name = format!(
+			"{FILE_PREFIX}{}.{}",
+			now.format("%Y-%m-%d_%H-%M-%S"),
+			self.extension
+		);
+
+		let file = File::options()
+			.create(true)
+			.append(true)
+			.open(self.dir.join(file_name))?;
+
+		let size = file.metadata()?.len();
+
+		self.remove_old_files();
+
+		Ok(Current { file, date: now.date_naive(), size })
+	}
+
+	/// Deletes everything but the newest [`RotatingFile::keep`] files.
+	fn remove_old_files(&self) {
+		let Ok(entries) = std::fs::read_dir(&self.dir) else {
+			return;
+		};
+
+		// The file names start with the date, so sorting them by name sorts them by age.
+		let mut files = entries
+			.filter_map(|entry| entry.ok())
+			.map(|entry| entry.path())
+			.filter(|path| {
+				path.file_name()
+					.and_then(|name| name.to_str())
+					.is_some_and(|name| name.starts_with(FILE_PREFIX))
+			})
+			.collect::<Vec<_>>();
+
+		files.sort();
+
+		let excess = files.len().saturating_sub(self.keep);
+
+		for path in &files[..excess] {
+			// Can't log here, that would end up right back in this writer.
+			let _ = std::fs::remove_file(path);
+		}
+	}
+}
+
+impl Write for &RotatingFile {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		let mut current = self
+			.current
+			.lock()
+			.map_err(|_| std::io::Error::other("log file is poisoned"))?;
+
+		let rotate = match &*current {
+			None => true,
+			Some(current) => self.needs_rotation(current, buf.len()),
+		};
+
+		if rotate {
+			*current = Some(self.open()?);
+		}
+
+		let Some(current) = current.as_mut() else {
+			return Ok(0);
+		};
+
+		current.file.write_all(buf)?;
+		current.size += buf.len() as u64;
+
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		match self.current.lock() {
+			Ok(mut current) => match current.as_mut() {
+				Some(current) => current.file.flush(),
+				None => Ok(()),
+			},
+			Err(_) => Ok(()),
+		}
+	}
+}
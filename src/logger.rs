@@ -1,44 +1,170 @@
 use {
-	crate::colors,
-	eframe::egui::RichText,
+	chrono::{DateTime, Utc},
+	color_eyre::{eyre::Context, Result},
+	serde_json::{Map, Value},
+	std::{
+		collections::VecDeque,
+		sync::{Arc, Mutex},
+	},
 	tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
-	tracing::error,
+	tracing::{error, info, Level},
+	tracing_subscriber::{reload, EnvFilter, Registry},
 };
 
+/// Changes which logs get recorded while the client is running.
+#[derive(Debug, Clone)]
+pub struct LogLevel {
+	handle: reload::Handle<EnvFilter, Registry>,
+	directives: Arc<Mutex<String>>,
+}
+
+impl LogLevel {
+	/// Falls back to `info` if `directives` are invalid.
+	pub fn new(directives: &str) -> (reload::Layer<EnvFilter, Registry>, Self) {
+		let (filter, directives) = match EnvFilter::try_new(directives) {
+			Ok(filter) => (filter, directives),
+			Err(_) => (EnvFilter::new("info"), "info"),
+		};
+
+		let (layer, handle) = reload::Layer::new(filter);
+		let directives = Arc::new(Mutex::new(String::from(directives)));
+
+		(layer, Self { handle, directives })
+	}
+
+	/// The directives that are currently active.
+	pub fn current(&self) -> String {
+		self.directives
+			.lock()
+			.map(|directives| directives.clone())
+			.unwrap_or_default()
+	}
+
+	/// `directives` use the same syntax as `RUST_LOG`, e.g. `info,schnose_gsi_client::gsi=trace`.
+	pub fn set(&self, directives: &str) -> Result<()> {
+		let filter = EnvFilter::try_new(directives).context("Invalid log level.")?;
+
+		self.handle
+			.reload(filter)
+			.context("Failed to change log level.")?;
+
+		if let Ok(mut current) = self.directives.lock() {
+			*current = String::from(directives);
+		}
+
+		info!("Changed log level to `{directives}`.");
+
+		Ok(())
+	}
+}
+
+/// How many logs are kept in memory by default.
+pub const DEFAULT_CAPACITY: usize = 10_000;
+
 #[derive(Debug, Clone)]
 pub struct LogSender {
 	emitter: UnboundedSender<Vec<u8>>,
 }
 
+/// Collects the JSON logs sent by [`LogSender`], parsing every line once.
 #[derive(Debug)]
 pub struct LogReceiver {
-	buffer: Vec<u8>,
 	receiver: UnboundedReceiver<Vec<u8>>,
+	/// The beginning of a line that hasn't been fully received yet.
+	partial: Vec<u8>,
+	logs: VecDeque<Log>,
+	capacity: usize,
+	/// How many logs were parsed in total, including the ones that were dropped already.
+	received: u64,
 }
 
 #[tracing::instrument]
 pub fn new() -> (LogSender, LogReceiver) {
 	let (sender, receiver) = mpsc::unbounded_channel();
 	let sender = LogSender { emitter: sender };
-	let receiver = LogReceiver { buffer: Vec::new(), receiver };
+	let receiver = LogReceiver {
+		receiver,
+		partial: Vec::new(),
+		logs: VecDeque::new(),
+		capacity: DEFAULT_CAPACITY,
+		received: 0,
+	};
 
 	(sender, receiver)
 }
 
-#[derive(Clone)]
+/// A single parsed log line.
+#[derive(Debug, Clone)]
 pub struct Log {
-	pub timestamp: RichText,
-	pub level: RichText,
-	pub message: RichText,
+	pub timestamp: DateTime<Utc>,
+	pub level: Level,
+	/// The module the log came from, e.g. `schnose_gsi_client::gsi`.
+	pub target: String,
+	pub file: Option<String>,
+	pub line: Option<u64>,
+	pub message: String,
+	/// Every field except `message`.
+	pub fields: Map<String, Value>,
+	/// The spans the log was emitted in, outermost first.
+	pub spans: Vec<Value>,
+	/// The original JSON line.
+	pub raw: String,
 }
 
 impl LogReceiver {
-	pub fn current(&mut self) -> Vec<u8> {
-		if let Ok(new_logs) = self.receiver.try_recv() {
-			self.buffer.extend(new_logs);
+	/// Parses everything that has been logged since the last call.
+	pub fn update(&mut self) {
+		while let Ok(bytes) = self.receiver.try_recv() {
+			self.partial.extend(bytes);
 		}
 
-		self.buffer.clone()
+		let Some(end) = self.partial.iter().rposition(|&byte| byte == b'\n') else {
+			return;
+		};
+
+		let complete = self.partial.drain(..=end).collect::<Vec<_>>();
+
+		for line in String::from_utf8_lossy(&complete).lines() {
+			if let Some(log) = Log::parse(line) {
+				self.logs.push_back(log);
+				self.received += 1;
+			}
+		}
+
+		self.truncate();
+	}
+
+	pub fn logs(&self) -> &VecDeque<Log> {
+		&self.logs
+	}
+
+	/// How many logs were received so far. Every log is numbered in the order it was received,
+	/// see [`LogReceiver::get`].
+	pub fn received(&self) -> u64 {
+		self.received
+	}
+
+	/// The number of the oldest log that is still kept.
+	pub fn first(&self) -> u64 {
+		self.received - self.logs.len() as u64
+	}
+
+	/// The log with the given number, unless it was dropped already.
+	pub fn get(&self, number: u64) -> Option<&Log> {
+		let idx = number.checked_sub(self.first())?;
+		self.logs.get(usize::try_from(idx).ok()?)
+	}
+
+	/// Keep at most `capacity` logs, dropping the oldest ones first.
+	pub fn set_capacity(&mut self, capacity: usize) {
+		self.capacity = capacity.max(1);
+		self.truncate();
+	}
+
+	fn truncate(&mut self) {
+		if self.logs.len() > self.capacity {
+			self.logs.drain(..self.logs.len() - self.capacity);
+		}
 	}
 }
 
@@ -49,7 +175,7 @@ impl std::io::Write for &LogSender {
 			Err(why) => {
 				let message = format!("Failed to send logs: {why:?}");
 				error!(message);
-				Err(std::io::Error::new(std::io::ErrorKind::Other, message))
+				Err(std::io::Error::other(message))
 			}
 		}
 	}
@@ -60,49 +186,151 @@ impl std::io::Write for &LogSender {
 }
 
 impl Log {
-	pub fn from_slice(logs: &[u8]) -> Vec<Self> {
-		String::from_utf8_lossy(logs)
-			.lines()
-			.filter_map(|line| {
-				let json = serde_json::from_str::<serde_json::Value>(line).ok()?;
-
-				let level = json.get("level")?.as_str()?;
-
-				let level = match level {
-					"TRACE" => RichText::new("[TRACE]").color(colors::TEAL),
-					"DEBUG" => RichText::new("[DEBUG]").color(colors::BLUE),
-					"INFO" => RichText::new("[INFO] ").color(colors::GREEN),
-					"WARN" => RichText::new("[WARN] ").color(colors::YELLOW),
-					"ERROR" => RichText::new("[ERROR]").color(colors::RED),
-					level => RichText::new(format!("[{level}]")).color(colors::MAUVE),
-				}
-				.monospace();
-
-				let (date, time) = json
-					.get("timestamp")?
-					.as_str()?
-					.split_once('T')?;
-
-				let (time, _) = time.split_once('.')?;
-
-				let timestamp = format!("{} {}", date.replace('-', "/"), time);
-
-				let timestamp = RichText::new(timestamp)
-					.color(colors::RED)
-					.monospace();
-
-				let message = json
-					.get("fields")?
-					.get("message")?
-					.as_str()?
-					.trim();
-
-				let message = RichText::new(message)
-					.color(colors::LAVENDER)
-					.monospace();
-
-				Some(Log { level, timestamp, message })
-			})
-			.collect()
+	/// Parses a line emitted by `tracing_subscriber`'s JSON formatter.
+	pub fn parse(line: &str) -> Option<Self> {
+		let mut json = match serde_json::from_str::<Value>(line).ok()? {
+			Value::Object(json) => json,
+			_ => return None,
+		};
+
+		let timestamp = DateTime::parse_from_rfc3339(json.get("timestamp")?.as_str()?)
+			.ok()?
+			.with_timezone(&Utc);
+
+		let level = json.get("level")?.as_str()?.parse().ok()?;
+
+		let mut fields = match json.remove("fields") {
+			Some(Value::Object(fields)) => fields,
+			_ => Map::new(),
+		};
+
+		let message = match fields.remove("message") {
+			Some(Value::String(message)) => message.trim().to_owned(),
+			_ => String::new(),
+		};
+
+		let spans = match json.remove("spans") {
+			Some(Value::Array(spans)) => spans,
+			_ => json.remove("span").into_iter().collect(),
+		};
+
+		let string = |key: &str| json.get(key).and_then(Value::as_str).map(String::from);
+
+		Some(Self {
+			timestamp,
+			level,
+			target: string("target").unwrap_or_default(),
+			file: string("filename"),
+			line: json.get("line_number").and_then(Value::as_u64),
+			message,
+			fields,
+			spans,
+			raw: line.to_owned(),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {super::*, serde_json::json, std::io::Write};
+
+	const LINE: &str = concat!(
+		r#"{"timestamp":"2023-04-20T12:00:00.000000Z","level":"INFO","#,
+		r#""fields":{"message":" Listening on port 8888. ","port":8888},"#,
+		r#""target":"schnose_gsi_client::gsi","filename":"src/gsi/mod.rs","line_number":42}"#,
+	);
+
+	#[test]
+	fn parse() {
+		let log = Log::parse(LINE).unwrap();
+
+		assert_eq!(log.timestamp.to_rfc3339(), "2023-04-20T12:00:00+00:00");
+		assert_eq!(log.level, Level::INFO);
+		assert_eq!(log.target, "schnose_gsi_client::gsi");
+		assert_eq!(log.file.as_deref(), Some("src/gsi/mod.rs"));
+		assert_eq!(log.line, Some(42));
+		assert_eq!(log.message, "Listening on port 8888.");
+		assert_eq!(Value::Object(log.fields), json!({ "port": 8888 }));
+		assert!(log.spans.is_empty());
+		assert_eq!(log.raw, LINE);
+	}
+
+	#[test]
+	fn parse_missing_fields() {
+		let log = Log::parse(r#"{"timestamp":"2023-04-20T12:00:00Z","level":"WARN"}"#).unwrap();
+
+		assert_eq!(log.level, Level::WARN);
+		assert_eq!(log.target, "");
+		assert_eq!(log.file, None);
+		assert_eq!(log.line, None);
+		assert_eq!(log.message, "");
+		assert!(log.fields.is_empty());
+
+		// Without a timestamp or level there is nothing to show.
+		assert!(Log::parse(r#"{"level":"INFO"}"#).is_none());
+		assert!(Log::parse(r#"{"timestamp":"2023-04-20T12:00:00Z"}"#).is_none());
+		assert!(Log::parse(r#"{"timestamp":"yesterday","level":"INFO"}"#).is_none());
+		assert!(Log::parse(r#"{"timestamp":"2023-04-20T12:00:00Z","level":"LOUD"}"#).is_none());
+	}
+
+	#[test]
+	fn parse_spans() {
+		let log = |spans: &str| {
+			let line = format!(r#"{{"timestamp":"2023-04-20T12:00:00Z","level":"INFO"{spans}}}"#);
+			Value::Array(Log::parse(&line).unwrap().spans)
+		};
+
+		assert_eq!(log(r#","span":{"name":"run"}"#), json!([{ "name": "run" }]));
+		assert_eq!(
+			log(r#","span":{"name":"run"},"spans":[{"name":"watch"},{"name":"run"}]"#),
+			json!([{ "name": "watch" }, { "name": "run" }])
+		);
+		assert_eq!(log(""), json!([]));
+	}
+
+	#[test]
+	fn parse_non_objects() {
+		for line in ["", "not json", "[]", "42", r#""a string""#, "null"] {
+			assert!(Log::parse(line).is_none(), "{line}");
+		}
+	}
+
+	#[test]
+	fn update_partial_lines() {
+		let (sender, mut receiver) = new();
+		let (start, end) = LINE.split_at(LINE.len() / 2);
+
+		(&sender).write_all(start.as_bytes()).unwrap();
+		receiver.update();
+		assert!(receiver.logs().is_empty());
+
+		(&sender).write_all(end.as_bytes()).unwrap();
+		(&sender).write_all(b"\n").unwrap();
+		(&sender).write_all(start.as_bytes()).unwrap();
+		receiver.update();
+		assert_eq!(receiver.logs().len(), 1);
+		assert_eq!(receiver.logs()[0].raw, LINE);
+
+		(&sender).write_all(format!("{end}\nnot json\n").as_bytes()).unwrap();
+		receiver.update();
+		assert_eq!(receiver.logs().len(), 2);
+	}
+
+	#[test]
+	fn capacity() {
+		let (sender, mut receiver) = new();
+		receiver.set_capacity(2);
+
+		for _ in 0..3 {
+			(&sender).write_all(format!("{LINE}\n").as_bytes()).unwrap();
+		}
+
+		receiver.update();
+		assert_eq!(receiver.logs().len(), 2);
+		assert_eq!(receiver.received(), 3);
+		assert_eq!(receiver.first(), 1);
+		assert!(receiver.get(0).is_none());
+		assert!(receiver.get(2).is_some());
+		assert!(receiver.get(3).is_none());
 	}
 }
@@ -1,20 +1,38 @@
 #![windows_subsystem = "windows"]
 
 use {
-	crate::{config::Config, gui::Client},
-	clap::Parser,
-	color_eyre::{eyre::Context, Result},
+	crate::{config::Config, gui::Client, logger::LogLevel},
+	clap::{Parser, Subcommand},
+	color_eyre::{
+		eyre::{bail as yeet, Context},
+		Result,
+	},
 	std::{path::PathBuf, sync::Arc},
-	tracing::Level,
-	tracing_subscriber::fmt::format::FmtSpan,
+	tracing_subscriber::{fmt::format::FmtSpan, layer::SubscriberExt, util::SubscriberInitExt},
 };
 
+mod assets;
 mod colors;
 mod config;
+mod diagnose;
+mod doctor;
 mod gsi;
 mod gui;
+mod history;
+mod html;
+mod log_export;
+mod log_files;
 mod logger;
+mod metrics;
+mod records;
+mod redact;
 mod server;
+mod session;
+mod status;
+mod steam;
+mod template;
+mod text_files;
+mod vdf;
 
 #[derive(Debug, Parser)]
 struct Args {
@@ -31,6 +49,47 @@ struct Args {
 	/// Use a custom config file.
 	#[arg(short, long = "config")]
 	config_path: Option<PathBuf>,
+
+	#[command(subcommand)]
+	command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+	/// Print the JSON Schema of the `/gsi` WebSocket protocol.
+	Schema,
+
+	/// Print the local history of played maps and fetched records.
+	History {
+		/// Only show entries from the last N days. `0` shows everything.
+		#[arg(long, default_value = "7")]
+		days: u32,
+
+		/// Only show entries for this map.
+		#[arg(long)]
+		map: Option<String>,
+
+		/// Only show records, no map changes.
+		#[arg(long)]
+		records: bool,
+
+		/// Print JSON lines instead of a human readable list.
+		#[arg(long)]
+		json: bool,
+	},
+
+	/// Collect config, logs and GSI setup into a zip file for support requests.
+	Diagnose {
+		/// Where to write the zip file. Defaults to the current directory.
+		#[arg(short, long)]
+		output: Option<PathBuf>,
+	},
+
+	/// Rewrite the GSI cfg if it doesn't match the current port or settings.
+	Repair,
+
+	/// Remove the GSI cfg from the game's cfg folder.
+	Uninstall,
 }
 
 #[tokio::main]
@@ -38,41 +97,147 @@ async fn main() -> Result<()> {
 	color_eyre::install()?;
 	let args = Args::parse();
 
-	let subscriber = tracing_subscriber::fmt()
-		.compact()
-		.with_file(true)
-		.with_line_number(true)
-		.with_span_events(FmtSpan::NEW)
-		.with_max_level(match args.debug {
-			true => Level::DEBUG,
-			false => Level::INFO,
-		});
+	match args.command {
+		None => {}
+		Some(Command::Schema) => {
+			let schema = serde_json::to_string_pretty(&server::protocol::schema())
+				.context("Failed to serialize schema.")?;
+
+			println!("{schema}");
+
+			return Ok(());
+		}
+		Some(Command::History { days, map, records, json }) => {
+			let since = chrono::Utc::now() - chrono::Duration::days(days.into());
+			let filter = history::Filter {
+				since: (days > 0).then_some(since),
+				map_name: map,
+				kind: records.then_some(history::Kind::Records),
+				..Default::default()
+			};
+
+			return history::print(&filter, json).await;
+		}
+		Some(Command::Diagnose { output }) => {
+			let config = load_config(args.config_path)?;
+			let output = output.unwrap_or_else(|| PathBuf::from(diagnose::default_file_name()));
+
+			diagnose::create(&config, None, &output).await?;
+			println!("Wrote diagnostics to `{}`.", output.display());
+
+			return Ok(());
+		}
+		Some(Command::Repair) => {
+			let config = load_config(args.config_path)?;
+			let cfg_dir = cfg_dir(&config)?;
+			let differences = gsi::cfg::repair(&cfg_dir, config.gsi_port)?;
+
+			if differences.is_empty() {
+				println!("`{}` is up to date.", gsi::cfg::path(&cfg_dir).display());
+			} else {
+				println!("Repaired GSI cfg: {}.", gsi::cfg::describe(&differences));
+				println!("Restart {} to pick up the changes.", config.game.name());
+			}
+
+			return Ok(());
+		}
+		Some(Command::Uninstall) => {
+			let config = load_config(args.config_path)?;
+			let cfg_dir = cfg_dir(&config)?;
+
+			if gsi::cfg::uninstall(&cfg_dir)? {
+				println!("Removed `{}`.", gsi::cfg::path(&cfg_dir).display());
+				println!("Restart {} to pick up the changes.", config.game.name());
+			} else {
+				println!("There is no GSI cfg in `{}`.", cfg_dir.display());
+			}
+
+			return Ok(());
+		}
+	}
+
+	let config = load_config(args.config_path)?;
+
+	// `--debug` wins over the config, but doesn't get saved.
+	let directives = match args.debug {
+		true => String::from("debug"),
+		false => config.log_level.clone(),
+	};
+
+	let (filter, log_level) = LogLevel::new(&directives);
+
+	// Logging isn't set up yet, so any error gets logged right after.
+	let (log_files, log_files_error) = match config.log_files.enabled {
+		false => (None, None),
+		true => match log_files::layer(&config.log_files) {
+			Ok(layer) => (Some(layer), None),
+			Err(why) => (None, Some(why)),
+		},
+	};
+
+	let registry = tracing_subscriber::registry().with(filter).with(log_files);
 
 	let logger = if args.log_to_stdout {
-		subscriber.init();
+		registry
+			.with(
+				tracing_subscriber::fmt::layer()
+					.compact()
+					.with_file(true)
+					.with_line_number(true)
+					.with_span_events(FmtSpan::NEW),
+			)
+			.init();
+
 		None
 	} else {
 		let (log_sender, log_receiver) = logger::new();
 
-		subscriber
-			.json()
-			.with_max_level(Level::DEBUG)
-			.with_writer(Arc::new(log_sender))
+		registry
+			.with(
+				tracing_subscriber::fmt::layer()
+					.json()
+					.with_file(true)
+					.with_line_number(true)
+					.with_span_events(FmtSpan::NEW)
+					.with_writer(Arc::new(log_sender)),
+			)
 			.init();
 
 		Some(log_receiver)
 	};
 
-	let config = match args.config_path {
-		None => Config::load().context("Failed to load config file.")?,
+	if let Some(why) = log_files_error {
+		tracing::error!("Failed to set up log files: {why:?}");
+	}
+
+	if let Err(why) = Config::create_themes_dir() {
+		tracing::error!("Failed to create themes folder: {why:?}");
+	}
+
+	Client::init(config, logger, log_level).await;
+
+	Ok(())
+}
+
+fn load_config(config_path: Option<PathBuf>) -> Result<Config> {
+	match config_path {
+		None => Config::load().context("Failed to load config file."),
 		Some(config_path) => {
 			let config_file =
 				std::fs::read_to_string(config_path).context("Failed to read config file.")?;
-			toml::from_str(&config_file).context("Failed to deserialize config file.")?
+			toml::from_str(&config_file).context("Failed to deserialize config file.")
 		}
-	};
+	}
+}
 
-	Client::init(config, logger).await;
+/// The configured cfg folder, or the first one found in the user's Steam libraries.
+fn cfg_dir(config: &Config) -> Result<PathBuf> {
+	if let Some(cfg_dir) = config.cfg_path().filter(|path| !path.as_os_str().is_empty()) {
+		return Ok(cfg_dir.clone());
+	}
 
-	Ok(())
+	match steam::find_cfg_dirs(config.game).into_iter().next() {
+		Some(cfg_dir) => Ok(cfg_dir),
+		None => yeet!("No cfg folder configured and none found automatically."),
+	}
 }
@@ -0,0 +1,250 @@
+//! Counters for diagnosing stream hiccups, served as Prometheus text on `GET /metrics`.
+//!
+//! Everything lives in [`METRICS`], so any part of the client can record something without
+//! having to pass a handle around.
+
+use {
+	gokz_rs::Error,
+	std::{
+		collections::BTreeMap,
+		fmt::Write,
+		future::Future,
+		sync::{
+			atomic::{AtomicI64, AtomicU64, Ordering},
+			Mutex,
+		},
+		time::Instant,
+	},
+	tracing::error,
+};
+
+pub static METRICS: Metrics = Metrics::new();
+
+/// Upper bounds of the request latency buckets, in seconds.
+const BUCKETS: [f64; 9] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, f64::INFINITY];
+
+#[derive(Debug)]
+pub struct Metrics {
+	gsi_events_received: AtomicU64,
+	gsi_events_deduplicated: AtomicU64,
+	gsi_events_failed: AtomicU64,
+	twitch_bot_notified: AtomicU64,
+	twitch_bot_failed: AtomicU64,
+	websocket_clients: AtomicI64,
+	/// Channel name -> number of skipped messages.
+	lagged: Mutex<BTreeMap<&'static str, u64>>,
+	/// Endpoint name -> latency histogram.
+	requests: Mutex<BTreeMap<&'static str, Histogram>>,
+}
+
+#[derive(Debug, Default)]
+struct Histogram {
+	buckets: [u64; BUCKETS.len()],
+	sum: f64,
+	count: u64,
+	errors: u64,
+}
+
+impl Metrics {
+	const fn new() -> Self {
+		Self {
+			gsi_events_received: AtomicU64::new(0),
+			gsi_events_deduplicated: AtomicU64::new(0),
+			gsi_events_failed: AtomicU64::new(0),
+			twitch_bot_notified: AtomicU64::new(0),
+			twitch_bot_failed: AtomicU64::new(0),
+			websocket_clients: AtomicI64::new(0),
+			lagged: Mutex::new(BTreeMap::new()),
+			requests: Mutex::new(BTreeMap::new()),
+		}
+	}
+
+	pub fn gsi_event_received(&self) {
+		self.gsi_events_received.fetch_add(1, Ordering::Relaxed);
+	}
+
+	/// The event was the same as the previous one and got ignored.
+	pub fn gsi_event_deduplicated(&self) {
+		self.gsi_events_deduplicated.fetch_add(1, Ordering::Relaxed);
+	}
+
+	/// The event couldn't be turned into a [`crate::gsi::State`].
+	pub fn gsi_event_failed(&self) {
+		self.gsi_events_failed.fetch_add(1, Ordering::Relaxed);
+	}
+
+	pub fn twitch_bot_notified(&self, success: bool) {
+		match success {
+			true => self.twitch_bot_notified.fetch_add(1, Ordering::Relaxed),
+			false => self.twitch_bot_failed.fetch_add(1, Ordering::Relaxed),
+		};
+	}
+
+	pub fn websocket_connected(&self) {
+		self.websocket_clients.fetch_add(1, Ordering::Relaxed);
+	}
+
+	pub fn websocket_disconnected(&self) {
+		self.websocket_clients.fetch_sub(1, Ordering::Relaxed);
+	}
+
+	/// A receiver of `channel` fell behind and skipped `skipped` messages.
+	pub fn lagged(&self, channel: &'static str, skipped: u64) {
+		match self.lagged.lock() {
+			Ok(mut lagged) => *lagged.entry(channel).or_default() += skipped,
+			Err(why) => error!("Metrics are poisoned: {why:?}"),
+		}
+	}
+
+	/// Runs a GlobalAPI request, recording how long it took and whether it failed.
+	///
+	/// Empty responses aren't counted as errors, that's just how the GlobalAPI says there is no
+	/// record or PB on a map.
+	pub async fn global_api<T>(
+		&self,
+		endpoint: &'static str,
+		request: impl Future<Output = Result<T, Error>>,
+	) -> Result<T, Error> {
+		let started = Instant::now();
+		let result = request.await;
+		let seconds = started.elapsed().as_secs_f64();
+
+		match self.requests.lock() {
+			Ok(mut requests) => {
+				let histogram = requests.entry(endpoint).or_default();
+
+				for (bucket, upper_bound) in histogram.buckets.iter_mut().zip(BUCKETS) {
+					if seconds <= upper_bound {
+						*bucket += 1;
+					}
+				}
+
+				histogram.sum += seconds;
+				histogram.count += 1;
+
+				if matches!(&result, Err(why) if !matches!(why, Error::EmptyResponse)) {
+					histogram.errors += 1;
+				}
+			}
+			Err(why) => error!("Metrics are poisoned: {why:?}"),
+		}
+
+		result
+	}
+
+	/// Everything in the Prometheus text format.
+	pub fn render(&self) -> String {
+		let mut text = String::new();
+
+		let mut metric = |name: &str, help: &str, kind: &str, samples: &[(String, String)]| {
+			let _ = writeln!(text, "# HELP {name} {help}");
+			let _ = writeln!(text, "# TYPE {name} {kind}");
+
+			for (labels, value) in samples {
+				let _ = writeln!(text, "{name}{labels} {value}");
+			}
+		};
+
+		let load = |value: &AtomicU64| value.load(Ordering::Relaxed).to_string();
+
+		metric(
+			"schnose_gsi_events_received_total",
+			"GSI events sent by CS:GO.",
+			"counter",
+			&[(String::new(), load(&self.gsi_events_received))],
+		);
+
+		metric(
+			"schnose_gsi_events_deduplicated_total",
+			"GSI events ignored because nothing changed.",
+			"counter",
+			&[(String::new(), load(&self.gsi_events_deduplicated))],
+		);
+
+		metric(
+			"schnose_gsi_events_failed_total",
+			"GSI events that couldn't be parsed.",
+			"counter",
+			&[(String::new(), load(&self.gsi_events_failed))],
+		);
+
+		metric(
+			"schnose_twitch_bot_notifications_total",
+			"Requests sent to the Twitch Bot.",
+			"counter",
+			&[
+				(String::from(r#"{outcome="success"}"#), load(&self.twitch_bot_notified)),
+				(String::from(r#"{outcome="failure"}"#), load(&self.twitch_bot_failed)),
+			],
+		);
+
+		metric(
+			"schnose_websocket_clients",
+			"Overlays currently connected to `/gsi`.",
+			"gauge",
+			&[(String::new(), self.websocket_clients.load(Ordering::Relaxed).to_string())],
+		);
+
+		let lagged = match self.lagged.lock() {
+			Ok(lagged) => lagged
+				.iter()
+				.map(|(channel, skipped)| {
+					(format!(r#"{{channel="{channel}"}}"#), skipped.to_string())
+				})
+				.collect::<Vec<_>>(),
+			Err(_) => Vec::new(),
+		};
+
+		metric(
+			"schnose_broadcast_lagged_messages_total",
+			"Messages skipped because a receiver fell behind.",
+			"counter",
+			&lagged,
+		);
+
+		let (latency, errors) = match self.requests.lock() {
+			Ok(requests) => {
+				let mut latency = Vec::new();
+				let mut errors = Vec::new();
+
+				for (endpoint, histogram) in requests.iter() {
+					for (count, upper_bound) in histogram.buckets.iter().zip(BUCKETS) {
+						let le = match upper_bound.is_infinite() {
+							true => String::from("+Inf"),
+							false => upper_bound.to_string(),
+						};
+
+						latency.push((
+							format!(r#"_bucket{{endpoint="{endpoint}",le="{le}"}}"#),
+							count.to_string(),
+						));
+					}
+
+					let labels = format!(r#"{{endpoint="{endpoint}"}}"#);
+					latency.push((format!("_sum{labels}"), histogram.sum.to_string()));
+					latency.push((format!("_count{labels}"), histogram.count.to_string()));
+					errors.push((labels, histogram.errors.to_string()));
+				}
+
+				(latency, errors)
+			}
+			Err(_) => (Vec::new(), Vec::new()),
+		};
+
+		metric(
+			"schnose_global_api_request_duration_seconds",
+			"Latency of GlobalAPI requests.",
+			"histogram",
+			&latency,
+		);
+
+		metric(
+			"schnose_global_api_request_errors_total",
+			"Failed GlobalAPI requests.",
+			"counter",
+			&errors,
+		);
+
+		text
+	}
+}
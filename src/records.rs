@@ -0,0 +1,540 @@
+use {
+	crate::{
+		config::Config,
+		gsi::{Game, State},
+		history::{EntryKind, History, Snapshot, Source},
+		metrics::METRICS,
+		status::Tracker,
+	},
+	chrono::NaiveDateTime,
+	color_eyre::{eyre::Context, Result},
+	gokz_rs::{global_api, MapIdentifier, Mode, SteamID},
+	schemars::JsonSchema,
+	serde::{Deserialize, Serialize},
+	std::{sync::Arc, time::Duration},
+	tokio::sync::{
+		broadcast::{error::RecvError, Receiver, Sender},
+		Mutex,
+	},
+	tracing::{debug, error, info, trace},
+};
+
+/// How often the GlobalAPI gets asked for new records.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Something noteworthy the streamer just did.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Event {
+	NewPersonalBest(Improvement),
+	NewWorldRecord(Improvement),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Improvement {
+	pub player_name: String,
+	#[schemars(with = "String")]
+	pub steam_id: SteamID,
+	pub map_name: String,
+	#[schemars(with = "String")]
+	pub mode: Mode,
+	pub teleports: bool,
+	pub old_time: Option<f64>,
+	pub new_time: f64,
+	/// `old_time - new_time`, if there was an old time.
+	pub improvement: Option<f64>,
+}
+
+/// The player, map and mode records are being watched for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Target {
+	player_name: String,
+	steam_id: SteamID,
+	map_name: String,
+	mode: Mode,
+}
+
+impl Target {
+	/// Records only exist for CS:GO. The GlobalAPI doesn't know about cs2kz runs, so a CS2 map
+	/// named like a CS:GO map would get PBs and WRs from the wrong leaderboard.
+	fn from_state(state: &State, game: Game) -> Option<Self> {
+		if game != Game::Csgo {
+			return None;
+		}
+
+		// Non-global maps don't have a tier and therefore no records either.
+		state.map_tier?;
+
+		Some(Self {
+			player_name: state.player_name.clone()?,
+			steam_id: state.steam_id?,
+			map_name: state.map_name.clone()?,
+			mode: state.mode?,
+		})
+	}
+}
+
+/// The parts of a GlobalAPI record overlays care about.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Record {
+	/// Time in seconds.
+	pub time: f64,
+	pub player_name: String,
+	#[schemars(with = "String")]
+	pub steam_id: SteamID,
+	/// When the record was set, in UTC.
+	#[schemars(with = "String")]
+	pub created_on: NaiveDateTime,
+}
+
+impl From<global_api::Record> for Record {
+	fn from(record: global_api::Record) -> Self {
+		Self {
+			time: record.time,
+			player_name: record.player_name,
+			steam_id: record.steam_id,
+			created_on: record.created_on,
+		}
+	}
+}
+
+/// World records and personal bests on the current map.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Records {
+	pub tp_wr: Option<Record>,
+	pub pro_wr: Option<Record>,
+	pub tp_pb: Option<Record>,
+	pub pro_pb: Option<Record>,
+}
+
+impl Records {
+	/// `None` if every request failed, which most likely means the GlobalAPI is unreachable.
+	///
+	/// Missing records are errors too, so a brand new map nobody has finished yet also ends up
+	/// as `None`.
+	async fn fetch(target: &Target, gokz_client: &gokz_rs::Client) -> Option<Self> {
+		let map = MapIdentifier::Name(target.map_name.clone());
+		let player = target.steam_id;
+		let mode = target.mode;
+
+		let (tp_wr, pro_wr, tp_pb, pro_pb) = tokio::join!(
+			METRICS.global_api(
+				"get_wr",
+				global_api::get_wr(map.clone(), mode, true, 0, gokz_client)
+			),
+			METRICS.global_api(
+				"get_wr",
+				global_api::get_wr(map.clone(), mode, false, 0, gokz_client)
+			),
+			METRICS.global_api(
+				"get_pb",
+				global_api::get_pb(player.into(), map.clone(), mode, true, 0, gokz_client)
+			),
+			METRICS.global_api(
+				"get_pb",
+				global_api::get_pb(player.into(), map, mode, false, 0, gokz_client)
+			),
+		);
+
+		if tp_wr.is_err() && pro_wr.is_err() && tp_pb.is_err() && pro_pb.is_err() {
+			return None;
+		}
+
+		Some(Self {
+			tp_wr: tp_wr.ok().map(Into::into),
+			pro_wr: pro_wr.ok().map(Into::into),
+			tp_pb: tp_pb.ok().map(Into::into),
+			pro_pb: pro_pb.ok().map(Into::into),
+		})
+	}
+
+	/// Records don't disappear, so a missing record in `new` is most likely a failed request.
+	/// Keep the old one around in that case so we don't report it again once the request
+	/// succeeds.
+	fn merge(self, new: Self) -> Self {
+		Self {
+			tp_wr: new.tp_wr.or(self.tp_wr),
+			pro_wr: new.pro_wr.or(self.pro_wr),
+			tp_pb: new.tp_pb.or(self.tp_pb),
+			pro_pb: new.pro_pb.or(self.pro_pb),
+		}
+	}
+}
+
+/// Compares successive [`Records`] for the same [`Target`] and figures out what changed.
+#[derive(Debug, Default)]
+struct RecordWatcher {
+	/// The current target and its baseline, once one could be fetched.
+	current: Option<(Target, Records)>,
+}
+
+impl RecordWatcher {
+	/// Returns the merged [`Records`] and any [`Event`]s that happened since the last update.
+	///
+	/// `new` is `None` if fetching failed. No events are emitted until a fetch for `target`
+	/// succeeded, otherwise every existing record would look new once the GlobalAPI is back.
+	fn update(&mut self, target: Target, new: Option<Records>) -> (Records, Vec<Event>) {
+		let old = match self.current.take() {
+			Some((old_target, old)) if old_target == target => old,
+			// New player, map or mode; this is our baseline.
+			_ => {
+				let Some(new) = new else {
+					return (Records::default(), Vec::new());
+				};
+
+				self.current = Some((target, new.clone()));
+				return (new, Vec::new());
+			}
+		};
+
+		let Some(new) = new else {
+			self.current = Some((target, old.clone()));
+			return (old, Vec::new());
+		};
+
+		let new = old.clone().merge(new);
+		let mut events = Vec::new();
+
+		for (teleports, old_pb, new_pb) in
+			[(true, &old.tp_pb, &new.tp_pb), (false, &old.pro_pb, &new.pro_pb)]
+		{
+			if let Some(improvement) = Self::improvement(&target, teleports, old_pb, new_pb) {
+				events.push(Event::NewPersonalBest(improvement));
+			}
+		}
+
+		for (teleports, old_wr, new_wr) in
+			[(true, &old.tp_wr, &new.tp_wr), (false, &old.pro_wr, &new.pro_wr)]
+		{
+			let is_ours = new_wr
+				.as_ref()
+				.is_some_and(|wr| wr.steam_id == target.steam_id);
+
+			if !is_ours {
+				continue;
+			}
+
+			if let Some(improvement) = Self::improvement(&target, teleports, old_wr, new_wr) {
+				events.push(Event::NewWorldRecord(improvement));
+			}
+		}
+
+		self.current = Some((target, new.clone()));
+
+		(new, events)
+	}
+
+	fn improvement(
+		target: &Target,
+		teleports: bool,
+		old: &Option<Record>,
+		new: &Option<Record>,
+	) -> Option<Improvement> {
+		let new_time = new.as_ref()?.time;
+		let old_time = old.as_ref().map(|record| record.time);
+
+		if old_time.is_some_and(|old_time| new_time >= old_time) {
+			return None;
+		}
+
+		Some(Improvement {
+			player_name: target.player_name.clone(),
+			steam_id: target.steam_id,
+			map_name: target.map_name.clone(),
+			mode: target.mode,
+			teleports,
+			old_time,
+			new_time,
+			improvement: old_time.map(|old_time| old_time - new_time),
+		})
+	}
+}
+
+/// Keeps track of the current [`State`] and checks the GlobalAPI for new records whenever the
+/// map changes and every [`POLL_INTERVAL`]. The current [`Records`] are sent to the overlay and
+/// any [`Event`]s are sent to the overlay, the Twitch Bot and all configured webhooks.
+///
+/// Records are added to the [`History`] whenever they differ from the previous fetch.
+pub async fn watch(
+	mut state_receiver: Receiver<State>,
+	records_sender: Sender<Records>,
+	event_sender: Sender<Event>,
+	config: Arc<Mutex<Config>>,
+	tracker: Arc<Mutex<Tracker>>,
+	history: Option<Arc<History>>,
+) {
+	let gokz_client = gokz_rs::Client::new();
+	let mut interval = tokio::time::interval(POLL_INTERVAL);
+	let mut watcher = RecordWatcher::default();
+	let mut target = None;
+	let mut recorded = None;
+
+	loop {
+		tokio::select! {
+			new_state = state_receiver.recv() => match new_state {
+				Ok(new_state) => {
+					let game = config.lock().await.game;
+					let new_target = Target::from_state(&new_state, game);
+
+					if new_target == target {
+						continue;
+					}
+
+					target = new_target;
+
+					// Clear the overlay right away, there won't be any records to fetch.
+					if target.is_none() {
+						if let Err(why) = records_sender.send(Records::default()) {
+							error!("Failed to send records: {why:?}");
+						}
+					}
+				}
+				Err(RecvError::Lagged(skipped)) => {
+					METRICS.lagged("records", skipped);
+					continue;
+				}
+				Err(RecvError::Closed) => break,
+			},
+
+			_ = interval.tick() => {}
+		}
+
+		let Some(target) = target.clone() else {
+			continue;
+		};
+
+		trace!("Checking for new records.");
+
+		let records = Records::fetch(&target, &gokz_client).await;
+
+		if records.is_none() {
+			debug!("Failed to fetch records for `{}`.", target.map_name);
+		}
+
+		let (records, events) = watcher.update(target.clone(), records);
+
+		if let Some(history) = &history {
+			let snapshot = (target, records.clone());
+
+			if recorded.as_ref() != Some(&snapshot) {
+				let (target, records) = snapshot.clone();
+				let entry = EntryKind::Records(Snapshot {
+					source: Source::Poll,
+					map_name: target.map_name,
+					mode: target.mode,
+					steam_id: Some(target.steam_id),
+					records,
+				});
+
+				match history.append(entry).await {
+					Ok(()) => recorded = Some(snapshot),
+					Err(why) => error!("Failed to record records: {why:?}"),
+				}
+			}
+		}
+
+		if let Err(why) = records_sender.send(records) {
+			error!("Failed to send records: {why:?}");
+		}
+
+		for event in events {
+			info!("New record: {event:?}");
+			dispatch(event, &event_sender, &config, &tracker, &gokz_client).await;
+		}
+	}
+}
+
+async fn dispatch(
+	event: Event,
+	event_sender: &Sender<Event>,
+	config: &Mutex<Config>,
+	tracker: &Mutex<Tracker>,
+	gokz_client: &gokz_rs::Client,
+) {
+	if let Err(why) = event_sender.send(event.clone()) {
+		error!("Failed to send event: {why:?}");
+	}
+
+	let (api_url, schnose_api_key, webhooks) = {
+		let config = config.lock().await;
+		(config.api_url.clone(), config.schnose_api_key, config.webhooks.clone())
+	};
+
+	if let Some(schnose_api_key) = schnose_api_key {
+		let api_url = format!("{}/events", api_url.trim_end_matches('/'));
+
+		let result =
+			crate::gsi::notify_twitch_bot(&event, &api_url, schnose_api_key, gokz_client).await;
+
+		if let Err(why) = &result {
+			error!("Failed to notify Twitch Bot with new event: {why:#?}");
+		}
+
+		tracker.lock().await.twitch_bot_notified(result);
+	}
+
+	for webhook in webhooks {
+		if let Err(why) = notify_webhook(&event, &webhook, gokz_client).await {
+			error!("Failed to notify webhook `{webhook}`: {why:#?}");
+		}
+	}
+}
+
+async fn notify_webhook(event: &Event, url: &str, gokz_client: &gokz_rs::Client) -> Result<()> {
+	let res = gokz_client
+		.post(url)
+		.json(event)
+		.send()
+		.await
+		.and_then(|res| res.error_for_status())
+		.context("Failed to POST event.")?;
+
+	debug!("{res:#?}");
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn target() -> Target {
+		Target {
+			player_name: String::from("AlphaKeks"),
+			steam_id: "STEAM_1:1:161178172".parse().unwrap(),
+			map_name: String::from("kz_lionharder"),
+			mode: Mode::SimpleKZ,
+		}
+	}
+
+	fn pb(time: f64) -> Option<Record> {
+		Some(Record {
+			time,
+			player_name: String::from("AlphaKeks"),
+			steam_id: target().steam_id,
+			created_on: NaiveDateTime::default(),
+		})
+	}
+
+	fn fetched(tp_pb: Option<Record>) -> Option<Records> {
+		Some(Records { tp_pb, ..Default::default() })
+	}
+
+	fn times(events: &[Event]) -> Vec<(Option<f64>, f64)> {
+		events
+			.iter()
+			.map(|event| match event {
+				Event::NewPersonalBest(improvement) | Event::NewWorldRecord(improvement) => {
+					(improvement.old_time, improvement.new_time)
+				}
+			})
+			.collect()
+	}
+
+	#[test]
+	fn first_poll_is_baseline() {
+		let mut watcher = RecordWatcher::default();
+		let (records, events) = watcher.update(target(), fetched(pb(60.0)));
+
+		assert_eq!(records.tp_pb, pb(60.0));
+		assert!(events.is_empty());
+	}
+
+	#[test]
+	fn improvement() {
+		let mut watcher = RecordWatcher::default();
+		watcher.update(target(), fetched(pb(60.0)));
+		let (records, events) = watcher.update(target(), fetched(pb(55.0)));
+
+		assert_eq!(records.tp_pb, pb(55.0));
+		assert_eq!(times(&events), [(Some(60.0), 55.0)]);
+		assert!(matches!(events[0], Event::NewPersonalBest(Improvement { teleports: true, .. })));
+	}
+
+	#[test]
+	fn first_pb_on_map() {
+		let mut watcher = RecordWatcher::default();
+		watcher.update(target(), fetched(None));
+		let (_, events) = watcher.update(target(), fetched(pb(60.0)));
+
+		assert_eq!(times(&events), [(None, 60.0)]);
+	}
+
+	#[test]
+	fn equal_time() {
+		let mut watcher = RecordWatcher::default();
+		watcher.update(target(), fetched(pb(60.0)));
+		let (_, events) = watcher.update(target(), fetched(pb(60.0)));
+
+		assert!(events.is_empty());
+	}
+
+	#[test]
+	fn slower_time() {
+		let mut watcher = RecordWatcher::default();
+		watcher.update(target(), fetched(pb(60.0)));
+		let (records, events) = watcher.update(target(), fetched(pb(65.0)));
+
+		assert_eq!(records.tp_pb, pb(65.0));
+		assert!(events.is_empty());
+	}
+
+	#[test]
+	fn missing_baseline() {
+		let mut watcher = RecordWatcher::default();
+		let (records, events) = watcher.update(target(), None);
+
+		assert_eq!(records, Records::default());
+		assert!(events.is_empty());
+
+		// The first successful fetch only sets the baseline.
+		let (_, events) = watcher.update(target(), fetched(pb(60.0)));
+		assert!(events.is_empty());
+
+		let (_, events) = watcher.update(target(), fetched(pb(55.0)));
+		assert_eq!(times(&events), [(Some(60.0), 55.0)]);
+	}
+
+	#[test]
+	fn failed_poll_keeps_baseline() {
+		let mut watcher = RecordWatcher::default();
+		watcher.update(target(), fetched(pb(60.0)));
+		let (records, events) = watcher.update(target(), None);
+
+		assert_eq!(records.tp_pb, pb(60.0));
+		assert!(events.is_empty());
+
+		let (_, events) = watcher.update(target(), fetched(pb(55.0)));
+		assert_eq!(times(&events), [(Some(60.0), 55.0)]);
+	}
+
+	#[test]
+	fn new_target_resets_baseline() {
+		let mut watcher = RecordWatcher::default();
+		watcher.update(target(), fetched(pb(60.0)));
+
+		let other = Target { mode: Mode::KZTimer, ..target() };
+		let (_, events) = watcher.update(other, fetched(pb(30.0)));
+
+		assert!(events.is_empty());
+	}
+
+	#[test]
+	fn only_csgo_has_targets() {
+		let target = target();
+		let state = State {
+			player_name: Some(target.player_name.clone()),
+			steam_id: Some(target.steam_id),
+			map_name: Some(target.map_name.clone()),
+			map_tier: Some(gokz_rs::Tier::Hard),
+			mode: Some(target.mode),
+		};
+
+		assert_eq!(Target::from_state(&state, Game::Csgo), Some(target));
+		assert_eq!(Target::from_state(&state, Game::Cs2), None);
+
+		// Non-global maps don't have records either.
+		let state = State { map_tier: None, ..state };
+		assert_eq!(Target::from_state(&state, Game::Csgo), None);
+	}
+}
@@ -0,0 +1,132 @@
+//! Masks secrets and personal information before logs or configs leave the user's machine.
+
+use {
+	crate::config::Config,
+	regex::{Captures, Regex},
+};
+
+pub const MASK: &str = "[REDACTED]";
+
+#[derive(Debug, Clone)]
+pub struct Redactor {
+	/// Exact values to mask, like the API key or webhook URLs.
+	secrets: Vec<String>,
+	steam_id: Regex,
+	/// `token: "..."` as it appears in `{event:#?}` dumps, possibly escaped inside JSON, and
+	/// `"token" "..."` as it appears in GSI cfgs.
+	token: Regex,
+}
+
+impl Redactor {
+	pub fn new(config: &Config) -> Self {
+		let mut secrets = config
+			.schnose_api_key
+			.map(|api_key| vec![api_key.to_string(), api_key.simple().to_string()])
+			.unwrap_or_default();
+
+		// Webhook URLs contain their own tokens.
+		secrets.extend(config.webhooks.iter().filter(|url| !url.is_empty()).cloned());
+
+		Self::with_secrets(secrets)
+	}
+
+	fn with_secrets(secrets: Vec<String>) -> Self {
+		Self {
+			secrets,
+			// STEAM_1:1:161178172, [U:1:322356345] and 76561198282622073
+			steam_id: Regex::new(r"STEAM_[0-5]:[01]:\d+|\[U:1:\d+\]|\b7656119\d{10}\b")
+				.expect("SteamID regex is valid"),
+			token: Regex::new(
+				r#"(?i)(token\\?"?\s*[:=]\s*\\?"?)[\w\-.]+|(token\\?"\s+\\?")[^"\\]+"#,
+			)
+			.expect("token regex is valid"),
+		}
+	}
+
+	pub fn redact(&self, text: &str) -> String {
+		let mut text = self
+			.secrets
+			.iter()
+			.fold(String::from(text), |text, secret| text.replace(secret.as_str(), MASK));
+
+		text = self.steam_id.replace_all(&text, MASK).into_owned();
+		text = self
+			.token
+			.replace_all(&text, |captures: &Captures| {
+				let key = captures.get(1).or_else(|| captures.get(2));
+				format!("{}{MASK}", key.map_or("", |key| key.as_str()))
+			})
+			.into_owned();
+
+		text
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Valve's example cfg, as shipped by plenty of HUDs and tools.
+	const THIRD_PARTY_CFG: &str = r#"
+"Console Sample v.1"
+{
+	"uri"		"http://127.0.0.1:3000"
+	"timeout"	"5.0"
+	"buffer"	"0.1"
+	"throttle"	"0.5"
+	"heartbeat"	"60.0"
+	"auth"
+	{
+		"token"	"CCWJu64ZV3JHDT8hZc"
+	}
+	"output"
+	{
+		"precision_time" "3"
+		"precision_position" "1"
+		"precision_vector" "3"
+	}
+	"data"
+	{
+		"provider"		"1"
+		"map"			"1"
+		"player_id"		"1"
+	}
+}
+"#;
+
+	#[test]
+	fn gsi_cfg_tokens() {
+		let redacted = Redactor::with_secrets(Vec::new()).redact(THIRD_PARTY_CFG);
+
+		assert!(!redacted.contains("CCWJu64ZV3JHDT8hZc"));
+		assert!(redacted.contains("\t\t\"token\"\t\"[REDACTED]\"\n"));
+		assert!(redacted.contains("\"uri\"\t\t\"http://127.0.0.1:3000\""));
+		assert!(redacted.contains("\"player_id\"\t\t\"1\""));
+	}
+
+	#[test]
+	fn debug_and_json_tokens() {
+		let redactor = Redactor::with_secrets(Vec::new());
+
+		let cases = [
+			(r#"token: "abc.def-1""#, r#"token: "[REDACTED]""#),
+			("token=abc", "token=[REDACTED]"),
+			(r#"{\"Token\": \"abc\"}"#, r#"{\"Token\": \"[REDACTED]\"}"#),
+			("the token expired", "the token expired"),
+		];
+
+		for (text, expected) in cases {
+			assert_eq!(redactor.redact(text), expected, "{text}");
+		}
+	}
+
+	#[test]
+	fn secrets_and_steam_ids() {
+		let redactor = Redactor::with_secrets(vec![String::from("hunter2")]);
+
+		assert_eq!(
+			redactor.redact("key hunter2 of STEAM_1:1:161178172 aka 76561198282622073"),
+			"key [REDACTED] of [REDACTED] aka [REDACTED]"
+		);
+	}
+}
@@ -0,0 +1,353 @@
+//! Live-reload for overlay development.
+//!
+//! While enabled, overlays get `live-reload.js` injected, which connects to `/dev/reload`.
+//! Whenever a file of the active theme (or the file in [`HOT_RELOAD_VAR`]) changes, stylesheets
+//! are swapped in place and anything else reloads the page.
+
+use {
+	super::StateReceiver,
+	crate::config::Config,
+	axum::{
+		extract::{
+			ws::{Message, WebSocketUpgrade},
+			State as StateExtractor,
+		},
+		response::IntoResponse,
+	},
+	notify::{RecursiveMode, Watcher},
+	serde::Serialize,
+	std::{
+		path::{Path, PathBuf},
+		sync::Arc,
+		time::Duration,
+	},
+	tokio::sync::{
+		broadcast::{error::RecvError, Sender},
+		mpsc, Mutex,
+	},
+	tracing::{error, info},
+};
+
+/// Serves the given HTML file instead of the built-in overlay and enables live-reload.
+pub const HOT_RELOAD_VAR: &str = "SCHNOSE_GSI_OVERLAY_HOT_RELOAD";
+
+/// Editors tend to touch files multiple times while saving.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Reload {
+	/// A stylesheet changed. `path` is relative to the theme's folder.
+	Css { path: String },
+	/// Anything else changed.
+	Page,
+}
+
+pub fn enabled(config: &Config) -> bool {
+	config.overlay_live_reload || hot_reload_path().is_some()
+}
+
+pub fn hot_reload_path() -> Option<PathBuf> {
+	std::env::var_os(HOT_RELOAD_VAR).map(PathBuf::from)
+}
+
+/// Adds the live-reload client to an HTML page.
+pub fn inject(html: &str) -> String {
+	let script = include_str!("../../assets/overlay/live-reload.js");
+	let script = format!("<script>{script}</script>");
+
+	match html.rfind("</body>") {
+		Some(idx) => {
+			let mut html = html.to_owned();
+			html.insert_str(idx, &script);
+			html
+		}
+		None => format!("{html}{script}"),
+	}
+}
+
+/// Watches the themes folder and the hot reload file and sends a [`Reload`] whenever one of the
+/// active overlay's files changes.
+pub async fn watch(config: Arc<Mutex<Config>>, sender: Sender<Reload>) {
+	let (path_sender, mut path_receiver) = mpsc::unbounded_channel();
+
+	let watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+		match event {
+			Ok(event) if !event.kind.is_access() => {
+				for path in event.paths {
+					// The receiver only goes away once we stopped caring.
+					let _ = path_sender.send(path);
+				}
+			}
+			Ok(_) => {}
+			Err(why) => error!("Failed to watch overlay files: {why:?}"),
+		}
+	});
+
+	let mut watcher = match watcher {
+		Ok(watcher) => watcher,
+		Err(why) => return error!("Failed to create file watcher: {why:?}"),
+	};
+
+	let themes_dir = match Config::themes_dir() {
+		Ok(themes_dir) => themes_dir,
+		Err(why) => return error!("Failed to find themes folder: {why:?}"),
+	};
+
+	if let Err(why) = watcher.watch(&themes_dir, RecursiveMode::Recursive) {
+		return error!("Failed to watch `{}`: {why:?}", themes_dir.display());
+	}
+
+	if let Some(hot_reload_path) = hot_reload_path() {
+		if let Err(why) = watcher.watch(&hot_reload_path, RecursiveMode::NonRecursive) {
+			error!("Failed to watch `{}`: {why:?}", hot_reload_path.display());
+		}
+	}
+
+	while let Some(path) = path_receiver.recv().await {
+		tokio::time::sleep(DEBOUNCE).await;
+
+		let mut paths = vec![path];
+		while let Ok(path) = path_receiver.try_recv() {
+			paths.push(path);
+		}
+
+		let theme_dir = {
+			let config = config.lock().await;
+
+			if !enabled(&config) {
+				continue;
+			}
+
+			config
+				.overlay_theme
+				.as_ref()
+				.map(|theme| themes_dir.join(theme))
+		};
+
+		let Some(reloads) = reloads(&paths, theme_dir.as_deref(), hot_reload_path().as_deref())
+		else {
+			continue;
+		};
+
+		for reload in reloads {
+			info!("Live-reload: {reload:?}");
+
+			// Nobody listening is fine.
+			let _ = sender.send(reload);
+		}
+	}
+}
+
+/// Figures out what overlays need to do about the changed `paths`, if anything.
+///
+/// Every path is canonicalized first, so symlinked or relative folders still match the paths
+/// reported by the file watcher.
+fn reloads(
+	paths: &[PathBuf],
+	theme_dir: Option<&Path>,
+	hot_reload_path: Option<&Path>,
+) -> Option<Vec<Reload>> {
+	let theme_dir = theme_dir.map(canonicalize);
+	let hot_reload_path = hot_reload_path.map(canonicalize);
+	let mut reloads = Vec::new();
+
+	for path in paths {
+		let path = canonicalize(path);
+
+		if Some(&path) == hot_reload_path.as_ref() {
+			return Some(vec![Reload::Page]);
+		}
+
+		let Some(relative) = theme_dir
+			.as_ref()
+			.and_then(|theme_dir| path.strip_prefix(theme_dir).ok())
+		else {
+			continue;
+		};
+
+		let is_css = relative
+			.extension()
+			.is_some_and(|extension| extension.eq_ignore_ascii_case("css"));
+
+		if !is_css {
+			return Some(vec![Reload::Page]);
+		}
+
+		let reload = Reload::Css {
+			path: relative
+				.components()
+				.map(|component| component.as_os_str().to_string_lossy())
+				.collect::<Vec<_>>()
+				.join("/"),
+		};
+
+		if !reloads.contains(&reload) {
+			reloads.push(reload);
+		}
+	}
+
+	(!reloads.is_empty()).then_some(reloads)
+}
+
+/// The absolute path without any symlinks, or `path` itself if it can't be resolved.
+fn canonicalize(path: &Path) -> PathBuf {
+	if let Ok(path) = path.canonicalize() {
+		return path;
+	}
+
+	// Deleted files can't be resolved anymore, but their folder usually still can.
+	let (Some(parent), Some(file_name)) = (path.parent(), path.file_name()) else {
+		return path.to_path_buf();
+	};
+
+	let parent = match parent.as_os_str().is_empty() {
+		true => Path::new("."),
+		false => parent,
+	};
+
+	parent
+		.canonicalize()
+		.map_or_else(|_| path.to_path_buf(), |parent| parent.join(file_name))
+}
+
+/// `GET /dev/reload`
+pub async fn websocket(
+	ws: WebSocketUpgrade,
+	StateExtractor(StateReceiver { reload_receiver, .. }): StateExtractor<StateReceiver>,
+) -> impl IntoResponse {
+	ws.on_upgrade(|mut ws| async move {
+		let mut receiver = reload_receiver.resubscribe();
+
+		loop {
+			let reload = match receiver.recv().await {
+				Ok(reload) => reload,
+				Err(RecvError::Lagged(_)) => Reload::Page,
+				Err(RecvError::Closed) => break,
+			};
+
+			let json = match serde_json::to_string(&reload) {
+				Ok(json) => json,
+				Err(why) => {
+					error!("Failed to serialize reload message: {why:?}");
+					continue;
+				}
+			};
+
+			if ws.send(Message::Text(json)).await.is_err() {
+				break;
+			}
+		}
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use {super::*, std::fs, tempfile::TempDir};
+
+	/// A themes folder with a `default` theme containing `files`.
+	fn themes(files: &[&str]) -> (TempDir, PathBuf) {
+		let temp_dir = TempDir::new().unwrap();
+		let theme_dir = temp_dir.path().join("themes").join("default");
+
+		for file in files {
+			let path = theme_dir.join(file);
+			fs::create_dir_all(path.parent().unwrap()).unwrap();
+			fs::write(path, "").unwrap();
+		}
+
+		(temp_dir, theme_dir)
+	}
+
+	fn css(path: &str) -> Reload {
+		Reload::Css { path: path.to_owned() }
+	}
+
+	#[test]
+	fn stylesheets() {
+		let (_temp_dir, theme_dir) = themes(&["style.css", "fonts/fonts.CSS"]);
+		let paths = [
+			theme_dir.join("style.css"),
+			theme_dir.join("fonts").join("fonts.CSS"),
+			theme_dir.join("style.css"),
+		];
+
+		assert_eq!(
+			reloads(&paths, Some(&theme_dir), None),
+			Some(vec![css("style.css"), css("fonts/fonts.CSS")])
+		);
+	}
+
+	#[test]
+	fn other_files() {
+		let (_temp_dir, theme_dir) = themes(&["style.css", "index.html"]);
+		let paths = [theme_dir.join("style.css"), theme_dir.join("index.html")];
+
+		assert_eq!(reloads(&paths, Some(&theme_dir), None), Some(vec![Reload::Page]));
+	}
+
+	#[test]
+	fn unrelated_files() {
+		let (temp_dir, theme_dir) = themes(&["style.css"]);
+		let other_theme = temp_dir.path().join("themes").join("other");
+		fs::create_dir_all(&other_theme).unwrap();
+		let paths = [other_theme.join("style.css"), temp_dir.path().join("config.toml")];
+
+		assert_eq!(reloads(&paths, Some(&theme_dir), None), None);
+		assert_eq!(reloads(&paths, None, None), None);
+	}
+
+	#[test]
+	fn deleted_files() {
+		let (_temp_dir, theme_dir) = themes(&[]);
+		fs::create_dir_all(&theme_dir).unwrap();
+		let paths = [theme_dir.join("removed.css")];
+
+		assert_eq!(reloads(&paths, Some(&theme_dir), None), Some(vec![css("removed.css")]));
+	}
+
+	#[test]
+	fn hot_reload_file() {
+		let (temp_dir, theme_dir) = themes(&["style.css"]);
+		let hot_reload_path = temp_dir.path().join("overlay.html");
+		fs::write(&hot_reload_path, "").unwrap();
+
+		// Same file, spelled differently.
+		let paths = [temp_dir.path().join("themes").join("..").join("overlay.html")];
+
+		assert_eq!(
+			reloads(&paths, Some(&theme_dir), Some(&hot_reload_path)),
+			Some(vec![Reload::Page])
+		);
+	}
+
+	#[test]
+	fn unresolved_theme_dir() {
+		let (temp_dir, theme_dir) = themes(&["style.css"]);
+		let unresolved = temp_dir.path().join("themes").join(".").join("default");
+
+		assert_eq!(
+			reloads(&[theme_dir.join("style.css")], Some(&unresolved), None),
+			Some(vec![css("style.css")])
+		);
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn symlinked_theme_dir() {
+		let (temp_dir, theme_dir) = themes(&["style.css"]);
+		let link = temp_dir.path().join("linked");
+		std::os::unix::fs::symlink(&theme_dir, &link).unwrap();
+
+		// The watcher may report either side of the link.
+		assert_eq!(
+			reloads(&[theme_dir.join("style.css")], Some(&link), None),
+			Some(vec![css("style.css")])
+		);
+
+		assert_eq!(
+			reloads(&[link.join("style.css")], Some(&theme_dir), None),
+			Some(vec![css("style.css")])
+		);
+	}
+}
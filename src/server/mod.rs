@@ -1,11 +1,19 @@
 use {
-	crate::gsi::State,
+	crate::{
+		config::Config,
+		gsi::State,
+		history::{EntryKind, History, Snapshot, Source},
+		logger::LogLevel,
+		metrics::METRICS,
+		records::{Event, Records},
+		session::{Session, Stats},
+		status::{Status, Tracker},
+		template::{self, Context, Texts},
+	},
 	axum::{
-		extract::{
-			ws::{Message, WebSocketUpgrade},
-			Query, State as StateExtractor,
-		},
-		response::{Html, IntoResponse},
+		extract::{Path, Query, State as StateExtractor},
+		http::{header, StatusCode},
+		response::{IntoResponse, Response},
 		routing::get,
 		Json, Router, Server,
 	},
@@ -13,61 +21,286 @@ use {
 		global_api::{self, Record},
 		MapIdentifier, Mode, SteamID,
 	},
+	schemars::schema::RootSchema,
 	serde::Deserialize,
-	std::{net::SocketAddr, path::PathBuf, sync::Arc},
-	tokio::sync::broadcast::Receiver,
-	tracing::error,
+	std::{collections::VecDeque, net::SocketAddr, sync::Arc},
+	tokio::sync::{
+		broadcast::{self, error::RecvError, Receiver, Sender},
+		Mutex,
+	},
+	tracing::{error, warn},
 };
 
+mod live_reload;
+mod overlay;
+pub mod protocol;
+mod rest;
+mod sse;
+pub mod themes;
+mod websocket;
+
 pub const PORT: u16 = 9999;
 
+/// Everything the server forwards to its clients.
+#[derive(Debug)]
+pub struct Receivers {
+	pub state: Receiver<State>,
+	pub records: Receiver<Records>,
+	pub events: Receiver<Event>,
+}
+
+/// How many messages are kept around for SSE clients resuming with `Last-Event-ID` and
+/// WebSocket clients that fell behind.
+pub const RECENT_MESSAGES: usize = 64;
+
+/// A [`protocol::Message`] with a sequential ID.
+#[derive(Debug, Clone)]
+pub struct Sequenced {
+	pub id: u64,
+	pub message: protocol::Message,
+}
+
+/// The most recent data of each kind, for clients that just connected or can't use WebSockets.
+#[derive(Debug, Default)]
+pub struct Latest {
+	pub state: State,
+	pub records: Records,
+	pub text: Texts,
+	/// The last [`RECENT_MESSAGES`] messages, oldest first.
+	pub recent: VecDeque<Sequenced>,
+	next_id: u64,
+}
+
+impl Latest {
+	/// Everything a client needs to know right after connecting.
+	pub fn snapshot(&self, status: Status, session: Stats) -> [protocol::Message; 5] {
+		[
+			protocol::Message::Status(status),
+			protocol::Message::Session(session),
+			protocol::Message::State(self.state.clone()),
+			protocol::Message::Records(self.records.clone()),
+			protocol::Message::Text(self.text.clone()),
+		]
+	}
+
+	/// Every message from `next_id` on, or `None` if some of them were already dropped from
+	/// [`Latest::recent`]. IDs start over at 0 whenever the client restarts, so IDs from an
+	/// earlier process are `None` too.
+	fn since(&self, next_id: u64) -> Option<impl Iterator<Item = &Sequenced>> {
+		let oldest_id = self
+			.recent
+			.front()
+			.map_or(self.next_id, |message| message.id);
+
+		(oldest_id <= next_id && next_id <= self.next_id).then(|| {
+			self.recent
+				.iter()
+				.filter(move |message| message.id >= next_id)
+		})
+	}
+
+	/// Remembers `message` and sends it to all clients.
+	fn publish(&mut self, message: protocol::Message, sender: &Sender<Sequenced>) {
+		match &message {
+			protocol::Message::State(state) => self.state = state.clone(),
+			protocol::Message::Records(records) => self.records = records.clone(),
+			protocol::Message::Text(text) => self.text = text.clone(),
+			_ => {}
+		}
+
+		let message = Sequenced { id: self.next_id, message };
+		self.next_id += 1;
+
+		if self.recent.len() == RECENT_MESSAGES {
+			self.recent.pop_front();
+		}
+
+		self.recent.push_back(message.clone());
+
+		if let Err(why) = sender.send(message) {
+			error!("Failed to forward message: {why:?}");
+		}
+	}
+}
+
 #[derive(Debug, Clone)]
 pub struct StateReceiver {
-	receiver: Arc<Receiver<State>>,
+	receiver: Arc<Receiver<Sequenced>>,
+	latest: Arc<Mutex<Latest>>,
+	tracker: Arc<Mutex<Tracker>>,
+	session: Arc<Mutex<Session>>,
+	history: Option<Arc<History>>,
+	log_level: LogLevel,
+	config: Arc<Mutex<Config>>,
+	reload_receiver: Arc<Receiver<live_reload::Reload>>,
 	gokz_client: Arc<gokz_rs::Client>,
 }
 
-pub async fn run(receiver: Receiver<State>) {
+pub async fn run(
+	receivers: Receivers,
+	tracker: Arc<Mutex<Tracker>>,
+	session: Arc<Mutex<Session>>,
+	history: Option<Arc<History>>,
+	log_level: LogLevel,
+	config: Arc<Mutex<Config>>,
+) {
+	let (message_sender, message_receiver) = broadcast::channel(64);
+	let latest = Arc::new(Mutex::new(Latest::default()));
+	let (reload_sender, reload_receiver) = broadcast::channel(16);
+
 	let state_receiver = StateReceiver {
-		receiver: Arc::new(receiver),
+		receiver: Arc::new(message_receiver),
+		latest: Arc::clone(&latest),
+		tracker,
+		session: Arc::clone(&session),
+		history,
+		log_level,
+		config: Arc::clone(&config),
+		reload_receiver: Arc::new(reload_receiver),
 		gokz_client: Arc::new(gokz_rs::Client::new()),
 	};
 
 	let addr = SocketAddr::from(([127, 0, 0, 1], PORT));
 
 	let router = Router::new()
-		.route("/", get(overlay))
-		.route("/gsi", get(websocket))
+		.route("/", get(overlay::default))
+		.route("/overlay", get(overlay::index))
+		.route("/overlay/:name", get(overlay::layout))
+		.route("/themes/:name", get(themes::redirect))
+		.route("/themes/:name/", get(themes::index))
+		.route("/themes/:name/*path", get(themes::file))
+		.route("/gsi", get(websocket::gsi))
+		.route("/events", get(sse::events))
+		.route("/dev/reload", get(live_reload::websocket))
+		.route("/assets/*path", get(assets))
+		.route("/schema", get(schema))
+		.route("/state", get(rest::state))
+		.route("/state/:file", get(rest::state_text))
+		.route("/status", get(rest::status))
+		.route("/health", get(rest::health))
+		.route("/session", get(rest::session))
+		.route("/session.csv", get(rest::session_csv))
+		.route("/history", get(rest::history))
+		.route("/metrics", get(rest::metrics))
+		.route("/admin/log-level", get(rest::log_level).put(rest::set_log_level))
 		.route("/wrs", get(wrs))
 		.route("/pbs", get(pbs))
 		.with_state(state_receiver);
 
-	Server::bind(&addr)
-		.serve(router.into_make_service())
-		.await
-		.expect("Failed to run Axum server.")
+	let server = Server::bind(&addr).serve(router.into_make_service());
+	let (_, _, result) = tokio::join!(
+		forward(receivers, message_sender, latest, session, Arc::clone(&config)),
+		live_reload::watch(config, reload_sender),
+		server,
+	);
+
+	result.expect("Failed to run Axum server.")
 }
 
-async fn websocket(
-	ws: WebSocketUpgrade,
-	StateExtractor(StateReceiver { receiver, .. }): StateExtractor<StateReceiver>,
-) -> impl IntoResponse {
-	ws.on_upgrade(|mut ws| async move {
-		let mut receiver = receiver.resubscribe();
-		while let Ok(state) = receiver.recv().await {
-			let json = match serde_json::to_string(&state) {
-				Ok(json) => json,
-				Err(why) => {
-					error!("Failed to serialize state: {why:?}");
-					continue;
+/// Merges all [`Receivers`] into a single stream of [`Sequenced`] messages, rendering the
+/// configured templates whenever the state or records change (and every
+/// [`template::REFRESH_INTERVAL`]) and keeping the [`Session`] up to date.
+async fn forward(
+	mut receivers: Receivers,
+	sender: Sender<Sequenced>,
+	latest: Arc<Mutex<Latest>>,
+	session: Arc<Mutex<Session>>,
+	config: Arc<Mutex<Config>>,
+) {
+	let mut refresh = tokio::time::interval(template::REFRESH_INTERVAL);
+
+	loop {
+		let message = tokio::select! {
+			state = receivers.state.recv() => state.map(protocol::Message::State),
+			records = receivers.records.recv() => records.map(protocol::Message::Records),
+			event = receivers.events.recv() => event.map(protocol::Message::Event),
+			_ = refresh.tick() => {
+				refresh_text(&sender, &latest, &config).await;
+				continue;
+			}
+		};
+
+		match message {
+			Ok(message) => {
+				let rerender = matches!(
+					message,
+					protocol::Message::State(_) | protocol::Message::Records(_)
+				);
+
+				let templates = match rerender {
+					true => config.lock().await.overlay_templates.clone(),
+					false => Texts::default(),
+				};
+
+				let session_stats = {
+					let mut session = session.lock().await;
+
+					let changed = match &message {
+						protocol::Message::State(state) => session.update(state),
+						protocol::Message::Event(event) => {
+							session.record(event);
+							true
+						}
+						_ => false,
+					};
+
+					changed.then(|| session.stats())
+				};
+
+				// Keep the lock while sending so SSE clients can't miss anything between reading
+				// `recent` and subscribing.
+				let mut latest = latest.lock().await;
+
+				latest.publish(message, &sender);
+
+				if !templates.is_empty() {
+					let context = Context { state: &latest.state, records: &latest.records };
+					let text = template::render_all(&templates, context);
+					latest.publish(protocol::Message::Text(text), &sender);
 				}
-			};
 
-			if let Err(why) = ws.send(Message::Text(json)).await {
-				error!("Failed to send state: {why:?}")
+				if let Some(stats) = session_stats {
+					latest.publish(protocol::Message::Session(stats), &sender);
+				}
 			}
+			Err(RecvError::Lagged(skipped)) => {
+				warn!("Skipped {skipped} messages.");
+				METRICS.lagged("forward", skipped);
+			}
+			Err(RecvError::Closed) => break,
 		}
-	})
+	}
+}
+
+/// Re-renders the templates, e.g. so `relative` dates don't get stuck at "just now".
+async fn refresh_text(sender: &Sender<Sequenced>, latest: &Mutex<Latest>, config: &Mutex<Config>) {
+	let templates = config.lock().await.overlay_templates.clone();
+	let mut latest = latest.lock().await;
+	let context = Context { state: &latest.state, records: &latest.records };
+	let text = template::render_all(&templates, context);
+
+	if text != latest.text {
+		latest.publish(protocol::Message::Text(text), sender);
+	}
+}
+
+async fn schema() -> Json<RootSchema> {
+	Json(protocol::schema())
+}
+
+/// `GET /assets/<path>`, files embedded into the binary.
+async fn assets(Path(path): Path<String>) -> Response {
+	let Some(contents) = crate::assets::get(&path) else {
+		return StatusCode::NOT_FOUND.into_response();
+	};
+
+	let headers = [
+		(header::CONTENT_TYPE, crate::assets::mime_type(std::path::Path::new(&path))),
+		// These can only change with a new version of the client.
+		(header::CACHE_CONTROL, "public, max-age=86400"),
+	];
+
+	(headers, contents).into_response()
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -77,52 +310,107 @@ struct GlobalAPIParams {
 	pub mode: Mode,
 }
 
-type Records = (Option<Record>, Option<Record>);
+/// TP and PRO, straight from the GlobalAPI.
+type GlobalRecords = (Option<Record>, Option<Record>);
 
 async fn wrs(
 	Query(GlobalAPIParams { map_identifier, mode, .. }): Query<GlobalAPIParams>,
-	StateExtractor(StateReceiver { gokz_client, .. }): StateExtractor<StateReceiver>,
-) -> Json<Records> {
-	let tp_wr = global_api::get_wr(map_identifier.clone(), mode, true, 0, &gokz_client)
+	StateExtractor(StateReceiver { gokz_client, history, .. }): StateExtractor<StateReceiver>,
+) -> Json<GlobalRecords> {
+	let tp_wr = METRICS
+		.global_api(
+			"get_wr",
+			global_api::get_wr(map_identifier.clone(), mode, true, 0, &gokz_client),
+		)
 		.await
 		.ok();
 
-	let pro_wr = global_api::get_wr(map_identifier, mode, false, 0, &gokz_client)
+	let pro_wr = METRICS
+		.global_api(
+			"get_wr",
+			global_api::get_wr(map_identifier.clone(), mode, false, 0, &gokz_client),
+		)
 		.await
 		.ok();
 
+	if let Some(history) = history {
+		let records = Records {
+			tp_wr: tp_wr.clone().map(Into::into),
+			pro_wr: pro_wr.clone().map(Into::into),
+			..Default::default()
+		};
+
+		record_history(&history, Source::Wrs, map_identifier, mode, None, records).await;
+	}
+
 	Json((tp_wr, pro_wr))
 }
 
 async fn pbs(
 	Query(GlobalAPIParams { steam_id, map_identifier, mode }): Query<GlobalAPIParams>,
-	StateExtractor(StateReceiver { gokz_client, .. }): StateExtractor<StateReceiver>,
-) -> Json<Records> {
-	let tp_pb =
-		global_api::get_pb(steam_id.into(), map_identifier.clone(), mode, true, 0, &gokz_client)
-			.await
-			.ok();
-
-	let pro_pb = global_api::get_pb(steam_id.into(), map_identifier, mode, false, 0, &gokz_client)
+	StateExtractor(StateReceiver { gokz_client, history, .. }): StateExtractor<StateReceiver>,
+) -> Json<GlobalRecords> {
+	let tp_pb = METRICS
+		.global_api(
+			"get_pb",
+			global_api::get_pb(
+				steam_id.into(),
+				map_identifier.clone(),
+				mode,
+				true,
+				0,
+				&gokz_client,
+			),
+		)
+		.await
+		.ok();
+
+	let pro_pb = METRICS
+		.global_api(
+			"get_pb",
+			global_api::get_pb(
+				steam_id.into(),
+				map_identifier.clone(),
+				mode,
+				false,
+				0,
+				&gokz_client,
+			),
+		)
 		.await
 		.ok();
 
+	if let Some(history) = history {
+		let records = Records {
+			tp_pb: tp_pb.clone().map(Into::into),
+			pro_pb: pro_pb.clone().map(Into::into),
+			..Default::default()
+		};
+
+		let steam_id = Some(steam_id);
+		record_history(&history, Source::Pbs, map_identifier, mode, steam_id, records).await;
+	}
+
 	Json((tp_pb, pro_pb))
 }
 
-async fn overlay() -> Html<String> {
-	Html(if let Ok(reload_path) = std::env::var("SCHNOSE_GSI_OVERLAY_HOT_RELOAD") {
-		let path = PathBuf::from(reload_path);
-		tokio::fs::read_to_string(path)
-			.await
-			.expect("Failed to read in HTML.")
-	} else {
-		let html = include_str!("../../assets/overlay/index.html");
-		let css = include_str!("../../assets/overlay/index.css");
-		let js = include_str!("../../assets/overlay/index.js")
-			.replace("__REPLACE_PORT__", &PORT.to_string());
-
-		html.replace("__REPLACE_CSS__", css)
-			.replace("__REPLACE_JS__", &js)
-	})
+async fn record_history(
+	history: &History,
+	source: Source,
+	map_identifier: MapIdentifier,
+	mode: Mode,
+	steam_id: Option<SteamID>,
+	records: Records,
+) {
+	let entry = EntryKind::Records(Snapshot {
+		source,
+		map_name: map_identifier.to_string(),
+		mode,
+		steam_id,
+		records,
+	});
+
+	if let Err(why) = history.append(entry).await {
+		error!("Failed to record records: {why:?}");
+	}
 }
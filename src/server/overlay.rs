@@ -0,0 +1,166 @@
+//! The built-in overlay layouts.
+//!
+//! Every layout is an HTML page with `__REPLACE_CSS__` and `__REPLACE_JS__` placeholders. The CSS
+//! is `index.css` followed by the layout's own CSS, the JS is always `index.js`.
+
+use {
+	super::{live_reload, themes, StateReceiver, PORT},
+	axum::{
+		extract::{Path, State as StateExtractor},
+		http::StatusCode,
+		response::{Html, IntoResponse, Redirect, Response},
+	},
+	tracing::error,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Layout {
+	pub name: &'static str,
+	pub description: &'static str,
+	html: &'static str,
+	css: &'static str,
+}
+
+pub const LAYOUTS: [Layout; 4] = [
+	Layout {
+		name: "compact",
+		description: "Map name and both WRs, stacked on top of each other.",
+		html: include_str!("../../assets/overlay/compact.html"),
+		css: include_str!("../../assets/overlay/compact.css"),
+	},
+	Layout {
+		name: "banner",
+		description: "Map name and both WRs in a single line.",
+		html: include_str!("../../assets/overlay/banner.html"),
+		css: include_str!("../../assets/overlay/banner.css"),
+	},
+	Layout {
+		name: "ticker",
+		description: "Only WRs and PBs, scrolling from right to left.",
+		html: include_str!("../../assets/overlay/ticker.html"),
+		css: include_str!("../../assets/overlay/ticker.css"),
+	},
+	Layout {
+		name: "sidebar",
+		description: "Everything in a vertical panel.",
+		html: include_str!("../../assets/overlay/sidebar.html"),
+		css: include_str!("../../assets/overlay/sidebar.css"),
+	},
+];
+
+impl Layout {
+	pub fn find(name: &str) -> Option<Self> {
+		LAYOUTS
+			.into_iter()
+			.find(|layout| layout.name == name)
+	}
+
+	pub async fn render(&self, live_reload: bool) -> String {
+		let html = self.render_html().await;
+
+		match live_reload {
+			true => live_reload::inject(&html),
+			false => html,
+		}
+	}
+
+	async fn render_html(&self) -> String {
+		if let Some(path) = live_reload::hot_reload_path() {
+			match tokio::fs::read_to_string(&path).await {
+				Ok(html) => return html,
+				Err(why) => error!(
+					"Failed to read in HTML from `{}`, falling back to built-in overlay: {why:?}",
+					path.display()
+				),
+			}
+		}
+
+		self.render_with(self.name, "")
+	}
+
+	/// Renders the layout as a standalone page, with `extra_css` appended to its own CSS.
+	/// Templates are looked up under `name` instead of the layout's name.
+	pub fn render_with(&self, name: &str, extra_css: &str) -> String {
+		let css = format!(
+			"{}\n{}\n{extra_css}",
+			include_str!("../../assets/overlay/index.css"),
+			self.css
+		);
+
+		let js = include_str!("../../assets/overlay/index.js")
+			.replace("__REPLACE_PORT__", &PORT.to_string())
+			.replace("__REPLACE_LAYOUT__", name);
+
+		self.html
+			.replace("__REPLACE_CSS__", &css)
+			.replace("__REPLACE_JS__", &js)
+	}
+}
+
+/// `GET /`
+///
+/// Redirects to the configured theme, if there is one, and shows an error page if that theme
+/// can't be found. Serves the built-in overlay otherwise.
+pub async fn default(
+	StateExtractor(StateReceiver { config, .. }): StateExtractor<StateReceiver>,
+) -> Response {
+	let (theme, live_reload) = {
+		let config = config.lock().await;
+		(config.overlay_theme.clone(), live_reload::enabled(&config))
+	};
+
+	if let Some(theme) = theme {
+		return match themes::resolve(&theme, "index.html") {
+			Ok(path) if path.is_file() => {
+				Redirect::temporary(&format!("/themes/{theme}/")).into_response()
+			}
+			Ok(path) => {
+				let message = format!("Theme `{theme}` is missing `{}`.", path.display());
+				error!("{message}");
+				themes::error_page(StatusCode::NOT_FOUND, &message)
+			}
+			Err(why) => {
+				error!("Failed to load theme `{theme}`: {why:?}");
+				themes::error_page(StatusCode::BAD_REQUEST, &format!("{why}"))
+			}
+		};
+	}
+
+	Html(LAYOUTS[0].render(live_reload).await).into_response()
+}
+
+/// `GET /overlay`, lists every layout with a live preview.
+pub async fn index() -> Html<String> {
+	let layouts = LAYOUTS
+		.iter()
+		.map(|Layout { name, description, .. }| {
+			format!(
+				r#"
+				<div class="layout">
+					<h2>{name}</h2>
+					<p>{description}</p>
+					<a href="/overlay/{name}">http://localhost:{PORT}/overlay/{name}</a>
+					<iframe src="/overlay/{name}"></iframe>
+				</div>
+				"#
+			)
+		})
+		.collect::<String>();
+
+	let html = include_str!("../../assets/overlay/layouts.html");
+
+	Html(html.replace("__REPLACE_LAYOUTS__", &layouts))
+}
+
+/// `GET /overlay/<name>`
+pub async fn layout(
+	Path(name): Path<String>,
+	StateExtractor(StateReceiver { config, .. }): StateExtractor<StateReceiver>,
+) -> Response {
+	let live_reload = live_reload::enabled(&*config.lock().await);
+
+	match Layout::find(&name) {
+		Some(layout) => Html(layout.render(live_reload).await).into_response(),
+		None => themes::error_page(StatusCode::NOT_FOUND, &format!("Unknown layout `{name}`.")),
+	}
+}
@@ -0,0 +1,132 @@
+//! Messages sent over `/gsi`.
+//!
+//! Every message from the server is wrapped in an [`Envelope`]:
+//!
+//! ```json
+//! { "v": 1, "type": "state", "data": { "map_name": "kz_lionharder", ... } }
+//! ```
+//!
+//! Clients may send a [`ClientMessage`] to pick which kinds of messages they want to receive. By
+//! default they receive everything.
+
+use {
+	crate::{
+		gsi::State,
+		records::{Event, Records},
+		session::Stats,
+		status::Status,
+		template::Texts,
+	},
+	schemars::{schema::RootSchema, JsonSchema},
+	serde::{Deserialize, Serialize},
+	std::{collections::HashSet, str::FromStr},
+};
+
+/// Bumped whenever a change to the protocol would break existing clients.
+pub const VERSION: u8 = 1;
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Envelope {
+	/// The protocol version; see [`VERSION`].
+	pub v: u8,
+	#[serde(flatten)]
+	pub message: Message,
+}
+
+impl From<Message> for Envelope {
+	fn from(message: Message) -> Self {
+		Self { v: VERSION, message }
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum Message {
+	State(State),
+	Records(Records),
+	Event(Event),
+	Status(Status),
+	/// Overlay name -> element class -> text, rendered from the configured templates.
+	Text(Texts),
+	/// Statistics about the current stream session.
+	Session(Stats),
+}
+
+impl Message {
+	pub const fn kind(&self) -> MessageKind {
+		match self {
+			Message::State(_) => MessageKind::State,
+			Message::Records(_) => MessageKind::Records,
+			Message::Event(_) => MessageKind::Event,
+			Message::Status(_) => MessageKind::Status,
+			Message::Text(_) => MessageKind::Text,
+			Message::Session(_) => MessageKind::Session,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageKind {
+	State,
+	Records,
+	Event,
+	Status,
+	Text,
+	Session,
+}
+
+impl MessageKind {
+	pub const ALL: [Self; 6] = [
+		Self::State,
+		Self::Records,
+		Self::Event,
+		Self::Status,
+		Self::Text,
+		Self::Session,
+	];
+
+	pub const fn as_str(&self) -> &'static str {
+		match self {
+			MessageKind::State => "state",
+			MessageKind::Records => "records",
+			MessageKind::Event => "event",
+			MessageKind::Status => "status",
+			MessageKind::Text => "text",
+			MessageKind::Session => "session",
+		}
+	}
+}
+
+impl FromStr for MessageKind {
+	type Err = String;
+
+	fn from_str(kind: &str) -> Result<Self, Self::Err> {
+		Self::ALL
+			.into_iter()
+			.find(|known| known.as_str() == kind)
+			.ok_or_else(|| format!("Unknown message type `{kind}`."))
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+	/// Only receive the given kinds of messages from now on.
+	Subscribe { types: HashSet<MessageKind> },
+}
+
+/// Both directions of the protocol, so they end up in a single schema.
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+struct Protocol {
+	/// Sent by the server.
+	server: Envelope,
+	/// Sent by clients.
+	client: ClientMessage,
+}
+
+/// JSON Schema describing every message sent over `/gsi`.
+pub fn schema() -> RootSchema {
+	schemars::schema_for!(Protocol)
+}
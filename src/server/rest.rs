@@ -0,0 +1,125 @@
+//! Plain HTTP endpoints for tools that can't use WebSockets.
+
+use {
+	super::StateReceiver,
+	crate::{
+		gsi::State,
+		history::{Entry, Filter},
+		metrics::METRICS,
+		session::Stats,
+		status::Status,
+	},
+	axum::{
+		extract::{Path, Query, State as StateExtractor},
+		http::{header, StatusCode},
+		response::IntoResponse,
+		Json,
+	},
+};
+
+/// `GET /state`
+pub async fn state(
+	StateExtractor(StateReceiver { latest, .. }): StateExtractor<StateReceiver>,
+) -> Json<State> {
+	Json(latest.lock().await.state.clone())
+}
+
+/// `GET /state/<field>.txt`, e.g. `/state/map.txt`
+pub async fn state_text(
+	Path(file): Path<String>,
+	StateExtractor(StateReceiver { latest, .. }): StateExtractor<StateReceiver>,
+) -> Result<String, StatusCode> {
+	let field = file
+		.strip_suffix(".txt")
+		.ok_or(StatusCode::NOT_FOUND)?;
+
+	let state = latest.lock().await.state.clone();
+
+	let text = match field {
+		"map" => state.map_name,
+		"tier" => state.map_tier.map(|tier| (tier as u8).to_string()),
+		"mode" => state.mode.map(|mode| State::mode_short(mode).to_owned()),
+		"player" => state.player_name,
+		"steam_id" => state.steam_id.map(|steam_id| steam_id.to_string()),
+		_ => return Err(StatusCode::NOT_FOUND),
+	};
+
+	Ok(text.unwrap_or_default())
+}
+
+/// `GET /status`
+pub async fn status(
+	StateExtractor(StateReceiver { tracker, .. }): StateExtractor<StateReceiver>,
+) -> Json<Status> {
+	Json(tracker.lock().await.status())
+}
+
+/// `GET /health`, for process supervisors.
+pub async fn health(
+	StateExtractor(StateReceiver { tracker, .. }): StateExtractor<StateReceiver>,
+) -> (StatusCode, &'static str) {
+	match tracker.lock().await.is_healthy() {
+		true => (StatusCode::OK, "OK"),
+		false => (StatusCode::SERVICE_UNAVAILABLE, "GSI listener is not running"),
+	}
+}
+
+/// `GET /session`
+pub async fn session(
+	StateExtractor(StateReceiver { session, .. }): StateExtractor<StateReceiver>,
+) -> Json<Stats> {
+	Json(session.lock().await.stats())
+}
+
+/// `GET /session.csv`, one line per map visit.
+pub async fn session_csv(
+	StateExtractor(StateReceiver { session, .. }): StateExtractor<StateReceiver>,
+) -> impl IntoResponse {
+	let csv = session.lock().await.to_csv();
+	([(header::CONTENT_TYPE, "text/csv; charset=utf-8")], csv)
+}
+
+/// `GET /history?since=<RFC 3339>&until=<RFC 3339>&map_name=<map>&kind=<state|records>`
+pub async fn history(
+	Query(filter): Query<Filter>,
+	StateExtractor(StateReceiver { history, .. }): StateExtractor<StateReceiver>,
+) -> Result<Json<Vec<Entry>>, (StatusCode, String)> {
+	let Some(history) = history else {
+		return Err((StatusCode::NOT_FOUND, String::from("History is disabled.")));
+	};
+
+	match history.query(&filter).await {
+		Ok(entries) => Ok(Json(entries)),
+		Err(why) => Err((StatusCode::INTERNAL_SERVER_ERROR, format!("{why}"))),
+	}
+}
+
+/// `GET /metrics`, in the Prometheus text format.
+pub async fn metrics() -> impl IntoResponse {
+	let headers = [(header::CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")];
+	(headers, METRICS.render())
+}
+
+/// `GET /admin/log-level`
+pub async fn log_level(
+	StateExtractor(StateReceiver { log_level, .. }): StateExtractor<StateReceiver>,
+) -> String {
+	log_level.current()
+}
+
+/// `PUT /admin/log-level` with `RUST_LOG`-style directives as the body, e.g. `debug`. The new
+/// level is saved in the config as well.
+pub async fn set_log_level(
+	StateExtractor(StateReceiver { log_level, config, .. }): StateExtractor<StateReceiver>,
+	directives: String,
+) -> Result<String, (StatusCode, String)> {
+	let directives = directives.trim();
+
+	if let Err(why) = log_level.set(directives) {
+		return Err((StatusCode::BAD_REQUEST, format!("{why}")));
+	}
+
+	config.lock().await.log_level = String::from(directives);
+
+	Ok(log_level.current())
+}
@@ -0,0 +1,199 @@
+//! `/events`, streaming the same messages as `/gsi` as Server-Sent Events.
+//!
+//! Every message carries its sequential ID, so clients reconnecting with `Last-Event-ID` receive
+//! whatever they missed, as long as it's still one of the last [`RECENT_MESSAGES`]. Everyone else
+//! gets a snapshot, like new clients.
+//!
+//! [`RECENT_MESSAGES`]: super::RECENT_MESSAGES
+
+use {
+	super::{
+		protocol::{self, Envelope, MessageKind},
+		Latest, Sequenced, StateReceiver,
+	},
+	crate::{metrics::METRICS, session::Stats, status::Status},
+	axum::{
+		extract::{Query, State as StateExtractor},
+		http::HeaderMap,
+		response::sse::{Event, KeepAlive, Sse},
+	},
+	futures::stream::{self, Stream, StreamExt},
+	serde::Deserialize,
+	std::{collections::HashSet, convert::Infallible},
+	tokio::sync::broadcast::error::RecvError,
+	tracing::error,
+};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventsParams {
+	/// Comma separated list of message types, e.g. `state,event`.
+	types: Option<String>,
+}
+
+/// `GET /events`
+pub async fn events(
+	headers: HeaderMap,
+	Query(EventsParams { types }): Query<EventsParams>,
+	StateExtractor(StateReceiver {
+		receiver,
+		latest,
+		tracker,
+		session,
+		..
+	}): StateExtractor<StateReceiver>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+	let subscriptions = match types {
+		None => HashSet::from(MessageKind::ALL),
+		Some(types) => types
+			.split(',')
+			.filter_map(|kind| kind.trim().parse().ok())
+			.collect(),
+	};
+
+	let last_event_id = headers
+		.get("last-event-id")
+		.and_then(|id| id.to_str().ok())
+		.and_then(|id| id.parse::<u64>().ok());
+
+	let status = tracker.lock().await.status();
+	let session = session.lock().await.stats();
+
+	// Subscribe while holding the lock, so nothing gets lost between reading the backlog and
+	// receiving live messages.
+	let (receiver, backlog) = {
+		let latest = latest.lock().await;
+		let receiver = receiver.resubscribe();
+
+		let backlog = backlog(&latest, last_event_id, status, session);
+
+		(receiver, backlog)
+	};
+
+	let live = stream::unfold(receiver, |mut receiver| async move {
+		loop {
+			match receiver.recv().await {
+				Ok(Sequenced { id, message }) => return Some(((Some(id), message), receiver)),
+				Err(RecvError::Lagged(skipped)) => {
+					METRICS.lagged("sse", skipped);
+					continue;
+				}
+				Err(RecvError::Closed) => return None,
+			}
+		}
+	});
+
+	let stream = stream::iter(backlog)
+		.chain(live)
+		.filter(move |(_, message)| std::future::ready(subscriptions.contains(&message.kind())))
+		.filter_map(|(id, message)| std::future::ready(sse_event(id, message).map(Ok)));
+
+	Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Everything a client that last saw `last_event_id` needs to catch up.
+///
+/// Clients can only resume if none of the messages they missed were dropped from
+/// [`Latest::recent`] yet; see [`Latest::since`]. Everyone else gets a snapshot, like `/gsi` sends
+/// to new clients.
+fn backlog(
+	latest: &Latest,
+	last_event_id: Option<u64>,
+	status: Status,
+	session: Stats,
+) -> Vec<(Option<u64>, protocol::Message)> {
+	let missed = last_event_id
+		.and_then(|last_event_id| last_event_id.checked_add(1))
+		.and_then(|next_id| latest.since(next_id));
+
+	match missed {
+		// Replay everything the client missed.
+		Some(missed) => missed
+			.map(|Sequenced { id, message }| (Some(*id), message.clone()))
+			.collect(),
+
+		None => latest
+			.snapshot(status, session)
+			.into_iter()
+			.map(|message| (None, message))
+			.collect(),
+	}
+}
+
+fn sse_event(id: Option<u64>, message: protocol::Message) -> Option<Event> {
+	let kind = message.kind().as_str();
+
+	let event = match Event::default().json_data(Envelope::from(message)) {
+		Ok(event) => event.event(kind),
+		Err(why) => {
+			error!("Failed to serialize message: {why:?}");
+			return None;
+		}
+	};
+
+	Some(match id {
+		Some(id) => event.id(id.to_string()),
+		None => event,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use {
+		super::*,
+		crate::{gsi::State, server::RECENT_MESSAGES, session::Session, status::Tracker},
+		tokio::sync::broadcast,
+	};
+
+	fn latest(messages: usize) -> Latest {
+		let (sender, _receiver) = broadcast::channel(RECENT_MESSAGES * 2);
+		let mut latest = Latest::default();
+
+		for _ in 0..messages {
+			latest.publish(protocol::Message::State(State::default()), &sender);
+		}
+
+		latest
+	}
+
+	fn ids(latest: &Latest, last_event_id: Option<u64>) -> Vec<Option<u64>> {
+		let status = Tracker::default().status();
+		let session = Session::default().stats();
+
+		backlog(latest, last_event_id, status, session)
+			.into_iter()
+			.map(|(id, _)| id)
+			.collect()
+	}
+
+	const SNAPSHOT: [Option<u64>; 5] = [None; 5];
+
+	#[test]
+	fn new_client() {
+		assert_eq!(ids(&latest(10), None), SNAPSHOT);
+	}
+
+	#[test]
+	fn resume() {
+		assert_eq!(ids(&latest(10), Some(6)), [Some(7), Some(8), Some(9)]);
+	}
+
+	#[test]
+	fn up_to_date() {
+		assert!(ids(&latest(10), Some(9)).is_empty());
+	}
+
+	#[test]
+	fn older_than_recent() {
+		let latest = latest(RECENT_MESSAGES + 10);
+
+		// 10 is the oldest message left, so 9 can still resume but 8 missed one.
+		assert_eq!(ids(&latest, Some(8)), SNAPSHOT);
+		assert_eq!(ids(&latest, Some(9)).len(), RECENT_MESSAGES);
+	}
+
+	#[test]
+	fn earlier_process() {
+		assert_eq!(ids(&latest(3), Some(50)), SNAPSHOT);
+		assert_eq!(ids(&latest(0), Some(0)), SNAPSHOT);
+	}
+}
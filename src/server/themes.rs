@@ -0,0 +1,206 @@
+//! User-supplied overlay themes.
+//!
+//! Every folder in [`Config::themes_dir`] containing an `index.html` is a theme. Its files are
+//! served as-is under `/themes/<name>/`, so relative links to CSS, JS and other assets just work.
+
+use {
+	super::{live_reload, overlay::Layout, StateReceiver},
+	crate::{
+		assets::mime_type,
+		colors,
+		config::{Config, OverlayStyle},
+		html,
+	},
+	axum::{
+		extract::{Path, State as StateExtractor},
+		http::{header, StatusCode},
+		response::{Html, IntoResponse, Redirect, Response},
+	},
+	color_eyre::{
+		eyre::{bail as yeet, Context},
+		Result,
+	},
+	std::path::{Component, PathBuf},
+	tracing::error,
+};
+
+/// Names of all installed themes.
+pub fn list() -> Result<Vec<String>> {
+	let themes_dir = Config::themes_dir()?;
+
+	if !themes_dir.exists() {
+		return Ok(Vec::new());
+	}
+
+	let mut themes = std::fs::read_dir(&themes_dir)
+		.context("Failed to read themes folder.")?
+		.filter_map(|entry| {
+			let path = entry.ok()?.path();
+
+			if !path.join("index.html").is_file() {
+				return None;
+			}
+
+			path.file_name()?.to_str().map(String::from)
+		})
+		.collect::<Vec<_>>();
+
+	themes.sort();
+
+	Ok(themes)
+}
+
+/// Resolves `path` inside of the theme called `name`, making sure it doesn't escape the theme's
+/// folder.
+pub fn resolve(name: &str, path: &str) -> Result<PathBuf> {
+	let is_safe = |path: &std::path::Path| {
+		path.components()
+			.all(|component| matches!(component, Component::Normal(_)))
+	};
+
+	let (name, path) = (std::path::Path::new(name), std::path::Path::new(path));
+
+	if name.components().count() != 1 || !is_safe(name) || !is_safe(path) {
+		yeet!("Invalid theme path `{}/{}`.", name.display(), path.display());
+	}
+
+	let mut full_path = Config::themes_dir()?;
+	full_path.push(name);
+	full_path.push(path);
+
+	if full_path.is_dir() {
+		full_path.push("index.html");
+	}
+
+	Ok(full_path)
+}
+
+/// Writes the overlay built in the editor tab to `<themes>/<style.name>/index.html`, overwriting
+/// any previous version of it.
+pub fn save(style: &OverlayStyle) -> Result<PathBuf> {
+	if style.name.trim().is_empty() {
+		yeet!("The theme needs a name.");
+	}
+
+	let path = resolve(&style.name, "index.html")?;
+	let theme_dir = path.parent().expect("`resolve` always returns a file inside a theme.");
+
+	std::fs::create_dir_all(theme_dir).context("Failed to create theme folder.")?;
+
+	let Some(compact) = Layout::find("compact") else {
+		yeet!("The `compact` layout is missing.");
+	};
+
+	let html = compact.render_with(&style.name, &css(style));
+
+	std::fs::write(&path, html).context("Failed to write theme.")?;
+
+	Ok(path)
+}
+
+/// CSS applying `style` on top of the `compact` layout.
+pub fn css(style: &OverlayStyle) -> String {
+	let color = |name: &str| {
+		let color = colors::by_name(name).unwrap_or(colors::TEXT);
+		format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+	};
+
+	let mut css = format!(
+		r#"
+* {{ font-family: "{font}", sans-serif; }}
+.map-name, .text {{ color: {text}; }}
+.map-name {{ font-size: {map_size}em; }}
+.wr {{ font-size: {records_size}em; }}
+.tp {{ color: {tp}; }}
+.pro {{ color: {pro}; }}
+.pb {{ color: {pb}; }}
+"#,
+		font = style.font,
+		text = color(&style.text_color),
+		map_size = style.map_size,
+		records_size = style.records_size,
+		tp = color(&style.tp_color),
+		pro = color(&style.pro_color),
+		pb = color(&style.pb_color),
+	);
+
+	let fields = style.fields();
+
+	for field in ["map", "tp", "pro", "pb", "events"] {
+		if !fields.contains(&field) {
+			css.push_str(&format!("[data-field=\"{field}\"] {{ display: none; }}\n"));
+		}
+	}
+
+	css
+}
+
+/// `GET /themes/<name>`, so relative links inside of the theme resolve correctly.
+pub async fn redirect(Path(name): Path<String>) -> Redirect {
+	Redirect::permanent(&format!("/themes/{name}/"))
+}
+
+/// `GET /themes/<name>/`
+pub async fn index(
+	Path(name): Path<String>,
+	StateExtractor(StateReceiver { config, .. }): StateExtractor<StateReceiver>,
+) -> Response {
+	let live_reload = live_reload::enabled(&*config.lock().await);
+	serve(&name, "", live_reload).await
+}
+
+/// `GET /themes/<name>/<path>`
+pub async fn file(
+	Path((name, path)): Path<(String, String)>,
+	StateExtractor(StateReceiver { config, .. }): StateExtractor<StateReceiver>,
+) -> Response {
+	let live_reload = live_reload::enabled(&*config.lock().await);
+	serve(&name, &path, live_reload).await
+}
+
+async fn serve(name: &str, path: &str, live_reload: bool) -> Response {
+	let full_path = match resolve(name, path) {
+		Ok(full_path) => full_path,
+		Err(why) => return error_page(StatusCode::BAD_REQUEST, &format!("{why}")),
+	};
+
+	let mime_type = mime_type(&full_path);
+
+	match tokio::fs::read(&full_path).await {
+		Ok(contents) if live_reload && mime_type.starts_with("text/html") => {
+			let html = live_reload::inject(&String::from_utf8_lossy(&contents));
+			([(header::CONTENT_TYPE, mime_type)], html).into_response()
+		}
+		Ok(contents) => ([(header::CONTENT_TYPE, mime_type)], contents).into_response(),
+		Err(why) => {
+			error!("Failed to read `{}`: {why:?}", full_path.display());
+			error_page(
+				StatusCode::NOT_FOUND,
+				&format!("Failed to read `{}`: {why}", full_path.display()),
+			)
+		}
+	}
+}
+
+/// Shown instead of an overlay if something went wrong.
+pub fn error_page(status: StatusCode, message: &str) -> Response {
+	let message = html::escape(message);
+	let html = format!(
+		r#"
+		<!DOCTYPE html>
+		<html lang="en">
+		<head>
+			<meta charset="UTF-8">
+			<title>SchnoseBot GSI Overlay</title>
+		</head>
+		<body style="font-family: sans-serif; color: #f38ba8;">
+			<h1>{status}</h1>
+			<p>{message}</p>
+			<p><a href="/overlay" style="color: #cba6f7;">Built-in overlays</a></p>
+		</body>
+		</html>
+		"#
+	);
+
+	(status, Html(html)).into_response()
+}
@@ -0,0 +1,508 @@
+//! `/gsi`, the WebSocket the overlays connect to.
+//!
+//! [`Connection`] decides what to send and when to give up on a client; [`serve`] only moves
+//! messages between it and the socket.
+
+use {
+	super::{
+		protocol::{self, ClientMessage, Envelope, MessageKind},
+		Latest, Sequenced, StateReceiver,
+	},
+	crate::{
+		metrics::METRICS,
+		session::{Session, Stats},
+		status::{Status, Tracker},
+	},
+	axum::{
+		extract::{
+			ws::{Message, WebSocket, WebSocketUpgrade},
+			State as StateExtractor,
+		},
+		response::IntoResponse,
+	},
+	color_eyre::{
+		eyre::{bail as yeet, Context as _},
+		Result,
+	},
+	std::{
+		collections::HashSet,
+		future::Future,
+		time::{Duration, Instant},
+	},
+	tokio::sync::{
+		broadcast::{error::RecvError, Receiver},
+		Mutex,
+	},
+	tracing::warn,
+};
+
+/// How often clients get pinged.
+const PING_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Clients that haven't sent anything (including pongs) for this long are considered dead.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long a single send may take before the client is considered stuck.
+const SEND_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// `GET /gsi`
+pub async fn gsi(
+	ws: WebSocketUpgrade,
+	StateExtractor(state_receiver): StateExtractor<StateReceiver>,
+) -> impl IntoResponse {
+	ws.on_upgrade(|ws| async move {
+		// Also counts connections whose task panics or gets cancelled as disconnected.
+		let _connection = ConnectionGuard::new();
+
+		if let Err(why) = serve(ws, state_receiver).await {
+			warn!("Closing WebSocket connection: {why}");
+		}
+	})
+}
+
+/// Counts a WebSocket connection in [`METRICS`] for as long as it's alive.
+struct ConnectionGuard;
+
+impl ConnectionGuard {
+	fn new() -> Self {
+		METRICS.websocket_connected();
+		Self
+	}
+}
+
+impl Drop for ConnectionGuard {
+	fn drop(&mut self) {
+		METRICS.websocket_disconnected();
+	}
+}
+
+/// What [`serve`] should do next.
+#[derive(Debug)]
+enum Action {
+	Nothing,
+	Send(protocol::Message),
+	/// The client fell behind by this many messages, see [`Connection::catch_up`].
+	CatchUp(u64),
+	Ping,
+	/// The client closed the connection.
+	Close,
+	/// Give up on the client.
+	Disconnect(String),
+}
+
+/// Everything the server knows about a single client.
+#[derive(Debug)]
+struct Connection {
+	subscriptions: HashSet<MessageKind>,
+	/// The ID of the next message the client should get, `None` until it got a snapshot.
+	next_id: Option<u64>,
+	last_seen: Instant,
+}
+
+impl Connection {
+	fn new(now: Instant) -> Self {
+		Self {
+			subscriptions: HashSet::from(MessageKind::ALL),
+			next_id: None,
+			last_seen: now,
+		}
+	}
+
+	fn on_broadcast(&mut self, message: Result<Sequenced, RecvError>) -> Action {
+		match message {
+			// Already sent while catching up.
+			Ok(Sequenced { id, .. }) if self.next_id.is_some_and(|next_id| id < next_id) => {
+				Action::Nothing
+			}
+			Ok(Sequenced { id, message }) => {
+				self.next_id = Some(id + 1);
+
+				match self.subscriptions.contains(&message.kind()) {
+					true => Action::Send(message),
+					false => Action::Nothing,
+				}
+			}
+			Err(RecvError::Lagged(skipped)) => Action::CatchUp(skipped),
+			Err(RecvError::Closed) => Action::Disconnect(String::from("Server is shutting down.")),
+		}
+	}
+
+	fn on_client_message(
+		&mut self,
+		message: Option<Result<Message, axum::Error>>,
+		now: Instant,
+	) -> Action {
+		self.last_seen = now;
+
+		match message {
+			Some(Ok(Message::Text(text))) => {
+				match serde_json::from_str::<ClientMessage>(&text) {
+					Ok(ClientMessage::Subscribe { types }) => self.subscriptions = types,
+					Err(why) => warn!("Invalid client message: {why:?}"),
+				}
+
+				Action::Nothing
+			}
+			Some(Ok(Message::Close(_))) | None => Action::Close,
+			// Pings are answered by axum, pongs only need to update `last_seen`.
+			Some(Ok(_)) => Action::Nothing,
+			Some(Err(why)) => Action::Disconnect(format!("Failed to receive message: {why:?}")),
+		}
+	}
+
+	fn on_ping_tick(&self, now: Instant) -> Action {
+		let silent_for = now.saturating_duration_since(self.last_seen);
+
+		match silent_for > CLIENT_TIMEOUT {
+			true => Action::Disconnect(format!(
+				"Client did not respond for {}s.",
+				silent_for.as_secs()
+			)),
+			false => Action::Ping,
+		}
+	}
+
+	/// Everything the client needs after connecting or falling behind.
+	///
+	/// Missed messages are replayed as long as none of them were dropped from
+	/// [`Latest::recent`] yet. Otherwise the client gets a snapshot of the current data plus the
+	/// missed events that are still around; older events are lost, since a snapshot only
+	/// restores state.
+	fn catch_up(
+		&mut self,
+		latest: &Latest,
+		status: Status,
+		session: Stats,
+	) -> Vec<protocol::Message> {
+		let missed = self.next_id.and_then(|next_id| latest.since(next_id));
+
+		let messages = match missed {
+			Some(missed) => missed
+				.map(|Sequenced { message, .. }| message.clone())
+				.collect::<Vec<_>>(),
+			None => {
+				let next_id = self.next_id.unwrap_or(latest.next_id);
+				let events = latest
+					.recent
+					.iter()
+					.filter(|Sequenced { id, message }| {
+						*id >= next_id && matches!(message, protocol::Message::Event(_))
+					})
+					.map(|Sequenced { message, .. }| message.clone());
+
+				latest
+					.snapshot(status, session)
+					.into_iter()
+					.chain(events)
+					.collect()
+			}
+		};
+
+		self.next_id = Some(latest.next_id);
+
+		messages
+			.into_iter()
+			.filter(|message| self.subscriptions.contains(&message.kind()))
+			.collect()
+	}
+}
+
+/// Runs until the client disconnects or stops responding.
+async fn serve(
+	mut ws: WebSocket,
+	StateReceiver {
+		receiver,
+		latest,
+		tracker,
+		session,
+		..
+	}: StateReceiver,
+) -> Result<()> {
+	let mut receiver = receiver.resubscribe();
+	let mut connection = Connection::new(Instant::now());
+	let mut ping_interval = tokio::time::interval(PING_INTERVAL);
+
+	// Catch up new clients so they don't have to wait for the next change.
+	catch_up(&mut ws, &mut connection, &mut receiver, &latest, &tracker, &session).await?;
+
+	loop {
+		let action = tokio::select! {
+			message = receiver.recv() => connection.on_broadcast(message),
+			message = ws.recv() => connection.on_client_message(message, Instant::now()),
+			_ = ping_interval.tick() => connection.on_ping_tick(Instant::now()),
+		};
+
+		match action {
+			Action::Nothing => {}
+			Action::Send(message) => send_message(&mut ws, message).await?,
+			Action::CatchUp(skipped) => {
+				METRICS.lagged("websocket", skipped);
+				warn!("WebSocket client fell behind by {skipped} messages, catching up.");
+
+				catch_up(&mut ws, &mut connection, &mut receiver, &latest, &tracker, &session)
+					.await?;
+			}
+			Action::Ping => send(&mut ws, Message::Ping(Vec::new())).await?,
+			Action::Close => return Ok(()),
+			Action::Disconnect(reason) => yeet!("{reason}"),
+		}
+	}
+}
+
+/// Resubscribes and sends whatever [`Connection::catch_up`] comes up with.
+///
+/// [`Latest`] stays locked while resubscribing, so nothing gets lost or sent twice in between.
+async fn catch_up(
+	ws: &mut WebSocket,
+	connection: &mut Connection,
+	receiver: &mut Receiver<Sequenced>,
+	latest: &Mutex<Latest>,
+	tracker: &Mutex<Tracker>,
+	session: &Mutex<Session>,
+) -> Result<()> {
+	let status = tracker.lock().await.status();
+	let session = session.lock().await.stats();
+
+	let messages = {
+		let latest = latest.lock().await;
+		*receiver = receiver.resubscribe();
+		connection.catch_up(&latest, status, session)
+	};
+
+	for message in messages {
+		send_message(ws, message).await?;
+	}
+
+	Ok(())
+}
+
+async fn send_message(ws: &mut WebSocket, message: protocol::Message) -> Result<()> {
+	let json = serde_json::to_string(&Envelope::from(message))
+		.context("Failed to serialize message.")?;
+	send(ws, Message::Text(json)).await
+}
+
+async fn send(ws: &mut WebSocket, message: Message) -> Result<()> {
+	send_within(SEND_TIMEOUT, ws.send(message)).await
+}
+
+/// Waits for `send`, giving up after `timeout` so one stuck client can't hold on to messages
+/// forever.
+async fn send_within<E>(timeout: Duration, send: impl Future<Output = Result<(), E>>) -> Result<()>
+where
+	E: std::error::Error + Send + Sync + 'static,
+{
+	match tokio::time::timeout(timeout, send).await {
+		Ok(result) => result.context("Failed to send message."),
+		Err(_) => yeet!("Sending took longer than {timeout:?}."),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {
+		super::*,
+		crate::{
+			gsi::State,
+			records::{Event, Improvement},
+			server::RECENT_MESSAGES,
+		},
+		gokz_rs::Mode,
+		tokio::sync::broadcast,
+	};
+
+	const SNAPSHOT: [MessageKind; 5] = [
+		MessageKind::Status,
+		MessageKind::Session,
+		MessageKind::State,
+		MessageKind::Records,
+		MessageKind::Text,
+	];
+
+	fn state() -> protocol::Message {
+		protocol::Message::State(State::default())
+	}
+
+	fn event() -> protocol::Message {
+		protocol::Message::Event(Event::NewPersonalBest(Improvement {
+			player_name: String::from("AlphaKeks"),
+			steam_id: "STEAM_1:1:161178172".parse().unwrap(),
+			map_name: String::from("kz_lionharder"),
+			mode: Mode::SimpleKZ,
+			teleports: false,
+			old_time: Some(60.0),
+			new_time: 55.0,
+			improvement: Some(5.0),
+		}))
+	}
+
+	/// [`Latest`] after publishing `count` messages, every odd one being an event.
+	fn latest(count: usize) -> Latest {
+		let (sender, _receiver) = broadcast::channel(1);
+		let mut latest = Latest::default();
+
+		for n in 0..count {
+			let message = if n % 2 == 1 { event() } else { state() };
+			latest.publish(message, &sender);
+		}
+
+		latest
+	}
+
+	fn sequenced(id: u64, message: protocol::Message) -> Result<Sequenced, RecvError> {
+		Ok(Sequenced { id, message })
+	}
+
+	fn catch_up(connection: &mut Connection, latest: &Latest) -> Vec<MessageKind> {
+		let status = Tracker::default().status();
+		let session = Session::default().stats();
+
+		connection
+			.catch_up(latest, status, session)
+			.iter()
+			.map(protocol::Message::kind)
+			.collect()
+	}
+
+	#[test]
+	fn new_client_gets_snapshot() {
+		let latest = latest(3);
+		let mut connection = Connection::new(Instant::now());
+
+		assert_eq!(catch_up(&mut connection, &latest), SNAPSHOT);
+		assert_eq!(connection.next_id, Some(3));
+	}
+
+	#[test]
+	fn forwards_subscribed_messages() {
+		let mut connection = Connection::new(Instant::now());
+		connection.next_id = Some(0);
+
+		assert!(matches!(connection.on_broadcast(sequenced(0, state())), Action::Send(_)));
+
+		connection.subscriptions = HashSet::from([MessageKind::Event]);
+
+		assert!(matches!(connection.on_broadcast(sequenced(1, state())), Action::Nothing));
+		assert!(matches!(connection.on_broadcast(sequenced(2, event())), Action::Send(_)));
+		assert_eq!(connection.next_id, Some(3));
+	}
+
+	#[test]
+	fn skips_messages_sent_while_catching_up() {
+		let mut connection = Connection::new(Instant::now());
+		connection.next_id = Some(5);
+
+		assert!(matches!(connection.on_broadcast(sequenced(4, state())), Action::Nothing));
+		assert_eq!(connection.next_id, Some(5));
+	}
+
+	#[test]
+	fn lagged() {
+		let mut connection = Connection::new(Instant::now());
+
+		assert!(matches!(
+			connection.on_broadcast(Err(RecvError::Lagged(7))),
+			Action::CatchUp(7)
+		));
+	}
+
+	#[test]
+	fn lag_replays_missed_messages() {
+		let latest = latest(5);
+		let mut connection = Connection::new(Instant::now());
+		connection.next_id = Some(2);
+
+		assert_eq!(
+			catch_up(&mut connection, &latest),
+			[MessageKind::State, MessageKind::Event, MessageKind::State]
+		);
+		assert_eq!(connection.next_id, Some(5));
+	}
+
+	#[test]
+	fn lag_beyond_recent_sends_snapshot_and_events() {
+		let latest = latest(RECENT_MESSAGES + 2);
+		let mut connection = Connection::new(Instant::now());
+		connection.next_id = Some(0);
+
+		let kinds = catch_up(&mut connection, &latest);
+
+		assert_eq!(kinds[..5], SNAPSHOT);
+		assert_eq!(kinds[5..], [MessageKind::Event; RECENT_MESSAGES / 2]);
+
+		connection.next_id = Some(0);
+		connection.subscriptions = HashSet::from([MessageKind::Event]);
+
+		assert_eq!(catch_up(&mut connection, &latest), [MessageKind::Event; RECENT_MESSAGES / 2]);
+	}
+
+	#[test]
+	fn server_closed() {
+		let mut connection = Connection::new(Instant::now());
+
+		assert!(matches!(
+			connection.on_broadcast(Err(RecvError::Closed)),
+			Action::Disconnect(_)
+		));
+	}
+
+	#[test]
+	fn client_messages() {
+		let now = Instant::now();
+		let mut connection = Connection::new(now);
+
+		let subscribe = Message::Text(String::from(r#"{"type":"subscribe","types":["state"]}"#));
+		assert!(matches!(connection.on_client_message(Some(Ok(subscribe)), now), Action::Nothing));
+		assert_eq!(connection.subscriptions, HashSet::from([MessageKind::State]));
+
+		let invalid = Message::Text(String::from("hi"));
+		assert!(matches!(connection.on_client_message(Some(Ok(invalid)), now), Action::Nothing));
+		assert_eq!(connection.subscriptions, HashSet::from([MessageKind::State]));
+
+		let error = axum::Error::new(std::io::Error::from(std::io::ErrorKind::ConnectionReset));
+		assert!(matches!(
+			connection.on_client_message(Some(Err(error)), now),
+			Action::Disconnect(_)
+		));
+
+		let close = Message::Close(None);
+		assert!(matches!(connection.on_client_message(Some(Ok(close)), now), Action::Close));
+		assert!(matches!(connection.on_client_message(None, now), Action::Close));
+	}
+
+	#[test]
+	fn ping_pong() {
+		let connected_at = Instant::now();
+		let mut connection = Connection::new(connected_at);
+
+		assert!(matches!(connection.on_ping_tick(connected_at + PING_INTERVAL), Action::Ping));
+
+		let pong_at = connected_at + CLIENT_TIMEOUT;
+		let pong = Message::Pong(Vec::new());
+		assert!(matches!(connection.on_client_message(Some(Ok(pong)), pong_at), Action::Nothing));
+		assert_eq!(connection.last_seen, pong_at);
+
+		assert!(matches!(connection.on_ping_tick(pong_at + CLIENT_TIMEOUT), Action::Ping));
+		assert!(matches!(
+			connection.on_ping_tick(pong_at + CLIENT_TIMEOUT + PING_INTERVAL),
+			Action::Disconnect(_)
+		));
+	}
+
+	#[tokio::test]
+	async fn send_timeouts() {
+		let timeout = Duration::from_millis(10);
+
+		let sent = send_within(timeout, async { Ok::<_, std::io::Error>(()) }).await;
+		assert!(sent.is_ok());
+
+		let failed = send_within(timeout, async {
+			Err(std::io::Error::from(std::io::ErrorKind::BrokenPipe))
+		})
+		.await;
+		assert!(failed.is_err());
+
+		let stuck = send_within(timeout, std::future::pending::<std::io::Result<()>>()).await;
+		assert!(stuck.unwrap_err().to_string().contains("took longer"));
+	}
+}
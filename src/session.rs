@@ -0,0 +1,260 @@
+//! Statistics about the current stream session, e.g. "maps played today: 7".
+
+use {
+	crate::{gsi::State, records::Event},
+	chrono::{DateTime, Utc},
+	gokz_rs::Mode,
+	schemars::JsonSchema,
+	serde::{Deserialize, Serialize},
+	std::{collections::BTreeMap, fmt::Write},
+};
+
+/// Fed with every [`State`] and [`Event`] while the server is running. Survives restarts of the
+/// server, only [`Session::reset`] starts a new session.
+#[derive(Debug)]
+pub struct Session {
+	started_at: DateTime<Utc>,
+	visits: Vec<Visit>,
+	mode: Option<Mode>,
+	mode_switches: u32,
+	personal_bests: u32,
+	world_records: u32,
+}
+
+impl Default for Session {
+	fn default() -> Self {
+		Self {
+			started_at: Utc::now(),
+			visits: Vec::new(),
+			mode: None,
+			mode_switches: 0,
+			personal_bests: 0,
+			world_records: 0,
+		}
+	}
+}
+
+/// A single stay on a map.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Visit {
+	pub map_name: String,
+	/// The mode the map was entered with, e.g. `SKZ`.
+	pub mode: Option<String>,
+	#[schemars(with = "String")]
+	pub entered_at: DateTime<Utc>,
+	/// `None` while still on the map.
+	#[schemars(with = "Option<String>")]
+	pub left_at: Option<DateTime<Utc>>,
+}
+
+impl Visit {
+	/// Seconds spent on the map so far.
+	pub fn duration(&self) -> f64 {
+		let left_at = self.left_at.unwrap_or_else(Utc::now);
+		(left_at - self.entered_at).num_milliseconds() as f64 / 1000.0
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Stats {
+	#[schemars(with = "String")]
+	pub started_at: DateTime<Utc>,
+	/// Number of different maps played.
+	pub maps_played: usize,
+	pub current_map: Option<String>,
+	/// Seconds spent on the current map.
+	pub time_on_current_map: Option<f64>,
+	/// Map name -> seconds spent on it in total.
+	pub time_per_map: BTreeMap<String, f64>,
+	pub mode_switches: u32,
+	pub personal_bests: u32,
+	pub world_records: u32,
+	/// Every map visit, oldest first.
+	pub visits: Vec<Visit>,
+}
+
+impl Session {
+	/// Returns whether anything changed.
+	///
+	/// Menus and non-KZ maps end the current visit without starting a new one.
+	pub fn update(&mut self, state: &State) -> bool {
+		let mut changed = false;
+
+		if let Some(mode) = state.mode {
+			if self.mode.is_some_and(|old_mode| old_mode != mode) {
+				self.mode_switches += 1;
+				changed = true;
+			}
+
+			self.mode = Some(mode);
+		}
+
+		let current_map = self.current().map(|visit| visit.map_name.as_str());
+		let new_map = state.known_map();
+
+		if current_map == new_map {
+			return changed;
+		}
+
+		let now = Utc::now();
+
+		if let Some(visit) = self.current_mut() {
+			visit.left_at = Some(now);
+		}
+
+		if let Some(map_name) = new_map {
+			self.visits.push(Visit {
+				map_name: map_name.to_owned(),
+				mode: state.mode.map(|mode| State::mode_short(mode).to_owned()),
+				entered_at: now,
+				left_at: None,
+			});
+		}
+
+		true
+	}
+
+	pub fn record(&mut self, event: &Event) {
+		match event {
+			Event::NewPersonalBest(_) => self.personal_bests += 1,
+			Event::NewWorldRecord(_) => self.world_records += 1,
+		}
+	}
+
+	pub fn reset(&mut self) {
+		*self = Self::default();
+	}
+
+	fn current(&self) -> Option<&Visit> {
+		self.visits
+			.last()
+			.filter(|visit| visit.left_at.is_none())
+	}
+
+	fn current_mut(&mut self) -> Option<&mut Visit> {
+		self.visits
+			.last_mut()
+			.filter(|visit| visit.left_at.is_none())
+	}
+
+	pub fn stats(&self) -> Stats {
+		let mut time_per_map = BTreeMap::<String, f64>::new();
+
+		for visit in &self.visits {
+			*time_per_map.entry(visit.map_name.clone()).or_default() += visit.duration();
+		}
+
+		let current = self.current();
+
+		Stats {
+			started_at: self.started_at,
+			maps_played: time_per_map.len(),
+			current_map: current.map(|visit| visit.map_name.clone()),
+			time_on_current_map: current.map(Visit::duration),
+			time_per_map,
+			mode_switches: self.mode_switches,
+			personal_bests: self.personal_bests,
+			world_records: self.world_records,
+			visits: self.visits.clone(),
+		}
+	}
+
+	/// One line per [`Visit`].
+	pub fn to_csv(&self) -> String {
+		let mut csv = String::from("map_name,mode,entered_at,left_at,seconds\n");
+
+		for visit in &self.visits {
+			let _ = writeln!(
+				csv,
+				"{},{},{},{},{:.0}",
+				visit.map_name,
+				visit.mode.as_deref().unwrap_or_default(),
+				visit.entered_at.to_rfc3339(),
+				visit
+					.left_at
+					.map(|left_at| left_at.to_rfc3339())
+					.unwrap_or_default(),
+				visit.duration(),
+			);
+		}
+
+		csv
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {super::*, crate::gsi::UNKNOWN_MAP};
+
+	fn state(map_name: &str, mode: Mode) -> State {
+		State {
+			map_name: Some(String::from(map_name)),
+			mode: Some(mode),
+			..Default::default()
+		}
+	}
+
+	fn maps(session: &Session) -> Vec<&str> {
+		session
+			.visits
+			.iter()
+			.map(|visit| visit.map_name.as_str())
+			.collect()
+	}
+
+	#[test]
+	fn map_changes() {
+		let mut session = Session::default();
+
+		assert!(session.update(&state("kz_lionharder", Mode::SimpleKZ)));
+		assert!(session.update(&state("kz_grotto", Mode::SimpleKZ)));
+		assert!(session.update(&state("kz_lionharder", Mode::SimpleKZ)));
+
+		assert_eq!(maps(&session), ["kz_lionharder", "kz_grotto", "kz_lionharder"]);
+		assert!(session.visits[..2].iter().all(|visit| visit.left_at.is_some()));
+
+		let stats = session.stats();
+		assert_eq!(stats.maps_played, 2);
+		assert_eq!(stats.current_map.as_deref(), Some("kz_lionharder"));
+	}
+
+	#[test]
+	fn mode_switches() {
+		let mut session = Session::default();
+
+		session.update(&state("kz_lionharder", Mode::SimpleKZ));
+		assert!(session.update(&state("kz_lionharder", Mode::KZTimer)));
+		session.update(&state("kz_grotto", Mode::KZTimer));
+		assert!(session.update(&state("kz_grotto", Mode::SimpleKZ)));
+
+		assert_eq!(session.mode_switches, 2);
+		assert_eq!(session.visits[1].mode.as_deref(), Some("KZT"));
+	}
+
+	#[test]
+	fn repeated_states() {
+		let mut session = Session::default();
+
+		assert!(session.update(&state("kz_lionharder", Mode::SimpleKZ)));
+		assert!(!session.update(&state("kz_lionharder", Mode::SimpleKZ)));
+		assert!(!session.update(&state("kz_lionharder", Mode::SimpleKZ)));
+
+		assert_eq!(maps(&session), ["kz_lionharder"]);
+		assert_eq!(session.mode_switches, 0);
+	}
+
+	#[test]
+	fn placeholders() {
+		let mut session = Session::default();
+
+		session.update(&state("kz_lionharder", Mode::SimpleKZ));
+		assert!(session.update(&state(UNKNOWN_MAP, Mode::SimpleKZ)));
+		assert!(!session.update(&state(UNKNOWN_MAP, Mode::SimpleKZ)));
+		assert!(!session.update(&State::default()));
+		session.update(&state("kz_grotto", Mode::SimpleKZ));
+
+		assert_eq!(maps(&session), ["kz_lionharder", "kz_grotto"]);
+		assert!(session.visits[0].left_at.is_some());
+		assert_eq!(session.stats().maps_played, 2);
+	}
+}
@@ -0,0 +1,91 @@
+use {
+	schemars::JsonSchema,
+	serde::{Deserialize, Serialize},
+	std::time::{Duration, Instant},
+};
+
+/// Keeps track of how the different parts of the client are doing.
+///
+/// Shared between the GSI listener, the record watcher and the HTTP server.
+#[derive(Debug, Default)]
+pub struct Tracker {
+	gsi_listening: bool,
+	listening_since: Option<Instant>,
+	last_event: Option<Instant>,
+	twitch_bot: TwitchBotStatus,
+	twitch_bot_error: Option<String>,
+}
+
+impl Tracker {
+	pub fn gsi_listening(&mut self, listening: bool) {
+		self.gsi_listening = listening;
+		self.listening_since = listening.then(Instant::now);
+	}
+
+	/// How long the GSI listener has been waiting for CS:GO.
+	pub fn listening_for(&self) -> Option<Duration> {
+		self.listening_since.map(|since| since.elapsed())
+	}
+
+	pub fn received_event(&mut self) {
+		self.last_event = Some(Instant::now());
+	}
+
+	pub fn twitch_bot_disabled(&mut self) {
+		self.twitch_bot = TwitchBotStatus::Disabled;
+		self.twitch_bot_error = None;
+	}
+
+	pub fn twitch_bot_notified<E: std::fmt::Display>(&mut self, result: Result<(), E>) {
+		match result {
+			Ok(()) => {
+				self.twitch_bot = TwitchBotStatus::Healthy;
+				self.twitch_bot_error = None;
+			}
+			Err(why) => {
+				self.twitch_bot = TwitchBotStatus::Failing;
+				self.twitch_bot_error = Some(why.to_string());
+			}
+		}
+	}
+
+	pub fn is_healthy(&self) -> bool {
+		self.gsi_listening
+	}
+
+	pub fn status(&self) -> Status {
+		Status {
+			version: String::from(env!("CARGO_PKG_VERSION")),
+			gsi_listening: self.gsi_listening,
+			last_event_age: self
+				.last_event
+				.map(|last_event| last_event.elapsed().as_secs_f64()),
+			twitch_bot: self.twitch_bot,
+			twitch_bot_error: self.twitch_bot_error.clone(),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Status {
+	pub version: String,
+	/// Whether the GSI listener is up and waiting for CS:GO.
+	pub gsi_listening: bool,
+	/// Seconds since the last GSI event, if there was one.
+	pub last_event_age: Option<f64>,
+	pub twitch_bot: TwitchBotStatus,
+	/// The last error that occurred while notifying the Twitch Bot.
+	pub twitch_bot_error: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TwitchBotStatus {
+	/// No API key configured.
+	Disabled,
+	/// Nothing has been sent yet.
+	#[default]
+	Waiting,
+	Healthy,
+	Failing,
+}
@@ -0,0 +1,302 @@
+//! Finds game installs by reading Steam's library folders, so users don't have to look for their
+//! cfg folder themselves.
+
+use {
+	crate::{gsi::Game, vdf},
+	std::path::{Path, PathBuf},
+};
+
+/// Every valid cfg folder of `game` across all Steam libraries on this machine.
+pub fn find_cfg_dirs(game: Game) -> Vec<PathBuf> {
+	find_cfg_dirs_in(&steam_dirs(), game)
+}
+
+/// Every valid cfg folder of `game` in the libraries of the given Steam installs.
+pub fn find_cfg_dirs_in(steam_dirs: &[PathBuf], game: Game) -> Vec<PathBuf> {
+	let mut cfg_dirs = Vec::<PathBuf>::new();
+
+	for library in steam_dirs.iter().flat_map(|steam_dir| libraries(steam_dir)) {
+		let cfg_dir = game
+			.cfg_path_in_library()
+			.iter()
+			.fold(library, |path, component| path.join(component));
+
+		if !game.looks_like_cfg_folder(&cfg_dir) {
+			continue;
+		}
+
+		// `~/.steam/steam` is usually a symlink to `~/.local/share/Steam`.
+		let cfg_dir = cfg_dir.canonicalize().unwrap_or(cfg_dir);
+
+		if !cfg_dirs.contains(&cfg_dir) {
+			cfg_dirs.push(cfg_dir);
+		}
+	}
+
+	cfg_dirs
+}
+
+/// The usual places Steam gets installed to.
+fn steam_dirs() -> Vec<PathBuf> {
+	let mut steam_dirs = Vec::new();
+
+	#[cfg(windows)]
+	{
+		for variable in ["ProgramFiles(x86)", "ProgramFiles"] {
+			if let Some(program_files) = std::env::var_os(variable) {
+				steam_dirs.push(PathBuf::from(program_files).join("Steam"));
+			}
+		}
+	}
+
+	#[cfg(unix)]
+	if let Some(home_dir) = std::env::var_os("HOME").map(PathBuf::from) {
+		steam_dirs.push(home_dir.join(".steam").join("steam"));
+		steam_dirs.push(home_dir.join(".steam").join("root"));
+		steam_dirs.push(home_dir.join(".local").join("share").join("Steam"));
+		// Flatpak
+		steam_dirs.push(
+			home_dir
+				.join(".var")
+				.join("app")
+				.join("com.valvesoftware.Steam")
+				.join(".local")
+				.join("share")
+				.join("Steam"),
+		);
+		// macOS
+		steam_dirs.push(
+			home_dir
+				.join("Library")
+				.join("Application Support")
+				.join("Steam"),
+		);
+	}
+
+	steam_dirs.retain(|steam_dir| steam_dir.is_dir());
+	steam_dirs
+}
+
+/// The Steam install itself plus every library listed in its `libraryfolders.vdf`.
+fn libraries(steam_dir: &Path) -> Vec<PathBuf> {
+	let mut libraries = vec![steam_dir.to_path_buf()];
+
+	for vdf_path in [
+		steam_dir.join("steamapps").join("libraryfolders.vdf"),
+		steam_dir.join("config").join("libraryfolders.vdf"),
+	] {
+		if let Ok(vdf) = std::fs::read_to_string(vdf_path) {
+			libraries.extend(parse_library_folders(&vdf));
+		}
+	}
+
+	libraries
+}
+
+/// Reads the library paths out of a `libraryfolders.vdf` file.
+///
+/// Newer files have a `"path"` key per library, older ones map indices straight to paths:
+///
+/// ```text
+/// "libraryfolders"
+/// {
+///     "0" { "path" "C:\\Program Files (x86)\\Steam" ... }
+///     "1" "D:\\SteamLibrary"
+/// }
+/// ```
+pub fn parse_library_folders(vdf: &str) -> Vec<PathBuf> {
+	vdf::parse(vdf)
+		.into_iter()
+		.filter(|pair| match pair.sections.len() {
+			1 => pair.key.chars().all(|char| char.is_ascii_digit()),
+			2 => pair.key.eq_ignore_ascii_case("path"),
+			_ => false,
+		})
+		.filter(|pair| !pair.value.is_empty())
+		.map(|pair| PathBuf::from(pair.value))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use {super::*, std::fs, tempfile::TempDir};
+
+	/// Creates an install of `game` inside of `library` and returns its cfg folder.
+	fn install(library: &Path, game: Game) -> PathBuf {
+		let cfg_dir = game
+			.cfg_path_in_library()
+			.iter()
+			.fold(library.to_path_buf(), |path, component| {
+				path.join(component)
+			});
+
+		fs::create_dir_all(&cfg_dir).unwrap();
+		fs::write(cfg_dir.parent().unwrap().join(marker_file(game)), "").unwrap();
+
+		cfg_dir.canonicalize().unwrap()
+	}
+
+	fn marker_file(game: Game) -> &'static str {
+		match game {
+			Game::Csgo => "gameinfo.txt",
+			Game::Cs2 => "gameinfo.gi",
+		}
+	}
+
+	/// Writes a `libraryfolders.vdf` listing `libraries` in the current format.
+	fn library_folders(libraries: &[&Path]) -> String {
+		let mut vdf = String::from("\"libraryfolders\"\n{\n");
+
+		for (idx, library) in libraries.iter().enumerate() {
+			let path = library.display().to_string().replace('\\', "\\\\");
+			vdf += &format!("\t\"{idx}\"\n\t{{\n\t\t\"path\" \"{path}\"\n\t}}\n");
+		}
+
+		vdf + "}\n"
+	}
+
+	#[test]
+	fn parse_current_format() {
+		let vdf = r#"
+			"libraryfolders"
+			{
+				"contentstatsid" "-1234"
+				"0"
+				{
+					"path" "C:\\Program Files (x86)\\Steam"
+					"label" ""
+					"apps" { "730" "12345" }
+				}
+				// A library on another drive
+				"1"
+				{
+					"Path" "D:\\SteamLibrary"
+				}
+			}
+		"#;
+
+		assert_eq!(
+			parse_library_folders(vdf),
+			[
+				PathBuf::from(r"C:\Program Files (x86)\Steam"),
+				PathBuf::from(r"D:\SteamLibrary"),
+			]
+		);
+	}
+
+	#[test]
+	fn parse_legacy_format() {
+		let vdf = r#"
+			"LibraryFolders"
+			{
+				"TimeNextStatsReport" "1600000000"
+				"ContentStatsID" "-1234"
+				"1" "D:\\SteamLibrary"
+				"2" "/mnt/games/steam"
+				"3" ""
+			}
+		"#;
+
+		assert_eq!(
+			parse_library_folders(vdf),
+			[
+				PathBuf::from(r"D:\SteamLibrary"),
+				PathBuf::from("/mnt/games/steam")
+			]
+		);
+	}
+
+	#[test]
+	fn steam_dir_itself() {
+		let steam = TempDir::new().unwrap();
+		let cfg_dir = install(steam.path(), Game::Csgo);
+
+		assert_eq!(
+			find_cfg_dirs_in(&[steam.path().to_path_buf()], Game::Csgo),
+			[cfg_dir]
+		);
+		assert!(find_cfg_dirs_in(&[steam.path().to_path_buf()], Game::Cs2).is_empty());
+	}
+
+	#[test]
+	fn both_vdf_locations() {
+		let steam = TempDir::new().unwrap();
+		let steamapps_library = TempDir::new().unwrap();
+		let config_library = TempDir::new().unwrap();
+
+		let steamapps_cfg = install(steamapps_library.path(), Game::Cs2);
+		let config_cfg = install(config_library.path(), Game::Cs2);
+
+		for (folder, library) in [
+			("steamapps", steamapps_library.path()),
+			("config", config_library.path()),
+		] {
+			let folder = steam.path().join(folder);
+			fs::create_dir_all(&folder).unwrap();
+			fs::write(
+				folder.join("libraryfolders.vdf"),
+				library_folders(&[library]),
+			)
+			.unwrap();
+		}
+
+		assert_eq!(
+			find_cfg_dirs_in(&[steam.path().to_path_buf()], Game::Cs2),
+			[steamapps_cfg, config_cfg]
+		);
+	}
+
+	#[test]
+	fn legacy_vdf() {
+		let steam = TempDir::new().unwrap();
+		let library = TempDir::new().unwrap();
+		let cfg_dir = install(library.path(), Game::Csgo);
+
+		let path = library.path().display().to_string().replace('\\', "\\\\");
+		let vdf = format!("\"LibraryFolders\"\n{{\n\t\"1\"\t\t\"{path}\"\n}}\n");
+
+		fs::create_dir_all(steam.path().join("steamapps")).unwrap();
+		fs::write(
+			steam.path().join("steamapps").join("libraryfolders.vdf"),
+			vdf,
+		)
+		.unwrap();
+
+		assert_eq!(
+			find_cfg_dirs_in(&[steam.path().to_path_buf()], Game::Csgo),
+			[cfg_dir]
+		);
+	}
+
+	#[test]
+	fn missing_marker_file() {
+		let steam = TempDir::new().unwrap();
+		let cfg_dir = install(steam.path(), Game::Csgo);
+
+		fs::remove_file(cfg_dir.parent().unwrap().join(marker_file(Game::Csgo))).unwrap();
+
+		assert!(cfg_dir.is_dir());
+		assert!(find_cfg_dirs_in(&[steam.path().to_path_buf()], Game::Csgo).is_empty());
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn symlinked_steam_dir() {
+		let home = TempDir::new().unwrap();
+		let steam = home.path().join(".local").join("share").join("Steam");
+		let symlink = home.path().join(".steam").join("steam");
+
+		let cfg_dir = install(&steam, Game::Csgo);
+		fs::create_dir_all(symlink.parent().unwrap()).unwrap();
+		std::os::unix::fs::symlink(&steam, &symlink).unwrap();
+
+		// The library lists itself as well, which is what Steam does.
+		fs::write(
+			steam.join("steamapps").join("libraryfolders.vdf"),
+			library_folders(&[&steam]),
+		)
+		.unwrap();
+
+		assert_eq!(find_cfg_dirs_in(&[symlink, steam], Game::Csgo), [cfg_dir]);
+	}
+}
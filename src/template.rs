@@ -0,0 +1,392 @@
+//! Format strings for overlay text, e.g. `{mode_short} · {map} · Tier {tier}`.
+//!
+//! Placeholders are written as `{variable}` or `{variable|formatter}`, formatters can be chained
+//! (`{mode|short|lower}`). Literal braces are written as `{{` and `}}`. Unknown variables and
+//! formatters are left as-is, so mistakes are easy to spot; variables without a value (e.g. no WR
+//! on the current map) render as an empty string.
+//!
+//! Variables:
+//! - `player`, `steam_id`
+//! - `map`, `tier`, `mode`, `mode_short`
+//! - `tp_wr_time`, `tp_wr_player`, `tp_wr_date` (and the same for `pro_wr`, `tp_pb`, `pro_pb`)
+//! - `tp_pb_diff`, `pro_pb_diff` (PB minus WR)
+//!
+//! Formatters:
+//! - times: `time` (default, `01:40.530`), `seconds` (`100.530`)
+//! - tiers: `number` (default), `name` (`Very Hard`)
+//! - modes: `long` (default, `KZTimer`), `short` (`KZT`), `api` (`kz_timer`)
+//! - dates: `relative` (default, `3 days ago`), `date` (`2023/04/20`)
+//! - anything: `upper`, `lower`
+//!
+//! Templates only get rendered when something changes, so the server re-renders them every
+//! [`REFRESH_INTERVAL`] to keep `relative` dates current.
+
+use {
+	crate::{
+		gsi::State,
+		records::{Record, Records},
+	},
+	chrono::{NaiveDateTime, Utc},
+	gokz_rs::{Mode, Tier},
+	std::{
+		collections::BTreeMap,
+		fmt::{self, Display},
+		time::Duration,
+	},
+};
+
+/// How often rendered templates should be refreshed; `relative` dates only change per minute.
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Everything templates can refer to.
+#[derive(Debug, Clone, Copy)]
+pub struct Context<'a> {
+	pub state: &'a State,
+	pub records: &'a Records,
+}
+
+#[derive(Debug, Clone)]
+enum Value {
+	Text(String),
+	Time(f64),
+	Tier(Tier),
+	Mode(Mode),
+	Date(NaiveDateTime),
+}
+
+impl Context<'_> {
+	/// `Err(())` if there is no such variable, `Ok(None)` if it just doesn't have a value.
+	fn get(&self, variable: &str) -> Result<Option<Value>, ()> {
+		let state = self.state;
+
+		Ok(match variable {
+			"player" => state.player_name.clone().map(Value::Text),
+			"steam_id" => state
+				.steam_id
+				.map(|steam_id| Value::Text(steam_id.to_string())),
+			"map" => state.map_name.clone().map(Value::Text),
+			"tier" => state.map_tier.map(Value::Tier),
+			"mode" => state.mode.map(Value::Mode),
+			"mode_short" => state
+				.mode
+				.map(|mode| Value::Text(State::mode_short(mode).to_owned())),
+			"tp_pb_diff" => Self::diff(&self.records.tp_pb, &self.records.tp_wr),
+			"pro_pb_diff" => Self::diff(&self.records.pro_pb, &self.records.pro_wr),
+			variable => {
+				let (record, field) = variable.rsplit_once('_').ok_or(())?;
+
+				let record = match record {
+					"tp_wr" => &self.records.tp_wr,
+					"pro_wr" => &self.records.pro_wr,
+					"tp_pb" => &self.records.tp_pb,
+					"pro_pb" => &self.records.pro_pb,
+					_ => return Err(()),
+				};
+
+				match field {
+					"time" => record.as_ref().map(|record| Value::Time(record.time)),
+					"player" => record
+						.as_ref()
+						.map(|record| Value::Text(record.player_name.clone())),
+					"date" => record
+						.as_ref()
+						.map(|record| Value::Date(record.created_on)),
+					_ => return Err(()),
+				}
+			}
+		})
+	}
+
+	fn diff(pb: &Option<Record>, wr: &Option<Record>) -> Option<Value> {
+		Some(Value::Time(pb.as_ref()?.time - wr.as_ref()?.time))
+	}
+}
+
+impl Value {
+	fn format(self, formatter: &str) -> Option<Self> {
+		Some(match (formatter, self) {
+			("upper", value) => Value::Text(value.to_string().to_uppercase()),
+			("lower", value) => Value::Text(value.to_string().to_lowercase()),
+
+			("time", Value::Time(seconds)) => Value::Text(format_time(seconds)),
+			("seconds", Value::Time(seconds)) => Value::Text(format!("{seconds:.3}")),
+
+			("number", Value::Tier(tier)) => Value::Text((tier as u8).to_string()),
+			("name", Value::Tier(tier)) => Value::Text(String::from(tier_name(tier))),
+
+			("long", Value::Mode(mode)) => Value::Text(String::from(mode_long(mode))),
+			("short", Value::Mode(mode)) => Value::Text(String::from(State::mode_short(mode))),
+			("api", Value::Mode(mode)) => Value::Text(String::from(mode_api(mode))),
+
+			("relative", Value::Date(date)) => Value::Text(format_relative(date)),
+			("date", Value::Date(date)) => Value::Text(date.format("%Y/%m/%d").to_string()),
+
+			_ => return None,
+		})
+	}
+}
+
+impl Display for Value {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Value::Text(text) => f.write_str(text),
+			Value::Time(seconds) => f.write_str(&format_time(*seconds)),
+			Value::Tier(tier) => write!(f, "{}", *tier as u8),
+			Value::Mode(mode) => f.write_str(mode_long(*mode)),
+			Value::Date(date) => f.write_str(&format_relative(*date)),
+		}
+	}
+}
+
+/// Overlay name -> element class -> rendered text.
+pub type Texts = BTreeMap<String, BTreeMap<String, String>>;
+
+/// Renders every template configured in [`Config::overlay_templates`].
+///
+/// [`Config::overlay_templates`]: crate::config::Config::overlay_templates
+pub fn render_all(templates: &Texts, context: Context<'_>) -> Texts {
+	templates
+		.iter()
+		.map(|(overlay, templates)| {
+			let texts = templates
+				.iter()
+				.map(|(class, template)| (class.clone(), render(template, context)))
+				.collect();
+
+			(overlay.clone(), texts)
+		})
+		.collect()
+}
+
+/// Renders `template` against `context`.
+pub fn render(template: &str, context: Context<'_>) -> String {
+	let mut output = String::with_capacity(template.len());
+	let mut rest = template;
+
+	while let Some(idx) = rest.find(['{', '}']) {
+		output.push_str(&rest[..idx]);
+		rest = &rest[idx..];
+
+		if rest.starts_with("{{") || rest.starts_with("}}") {
+			output.push_str(&rest[..1]);
+			rest = &rest[2..];
+			continue;
+		}
+
+		if rest.starts_with('}') {
+			output.push('}');
+			rest = &rest[1..];
+			continue;
+		}
+
+		let Some(end) = rest.find('}') else {
+			break;
+		};
+
+		let placeholder = &rest[..=end];
+		let rendered = render_placeholder(&placeholder[1..end], context)
+			.unwrap_or_else(|| placeholder.to_owned());
+
+		output.push_str(&rendered);
+		rest = &rest[end + 1..];
+	}
+
+	output.push_str(rest);
+	output
+}
+
+/// `None` if the placeholder is invalid.
+fn render_placeholder(placeholder: &str, context: Context<'_>) -> Option<String> {
+	let mut parts = placeholder.split('|').map(str::trim);
+	let variable = parts.next()?;
+
+	let Some(mut value) = context.get(variable).ok()? else {
+		// Still make sure the formatters exist.
+		return parts
+			.all(|formatter| FORMATTERS.contains(&formatter))
+			.then(String::new);
+	};
+
+	for formatter in parts {
+		value = value.format(formatter)?;
+	}
+
+	Some(value.to_string())
+}
+
+const FORMATTERS: [&str; 11] = [
+	"upper", "lower", "time", "seconds", "number", "name", "long", "short", "api", "relative",
+	"date",
+];
+
+/// 100.53 => 01:40.530
+pub fn format_time(seconds: f64) -> String {
+	let sign = if seconds < 0.0 { "-" } else { "" };
+
+	// Round first so 59.9999 becomes 01:00.000 instead of 00:60.000.
+	let millis = (seconds.abs() * 1000.0).round() as u64;
+	let hours = millis / (60 * 60 * 1000);
+	let minutes = millis / (60 * 1000) % 60;
+	let seconds = (millis % (60 * 1000)) as f64 / 1000.0;
+
+	match hours > 0 {
+		true => format!("{sign}{hours:02}:{minutes:02}:{seconds:06.3}"),
+		false => format!("{sign}{minutes:02}:{seconds:06.3}"),
+	}
+}
+
+fn format_relative(date: NaiveDateTime) -> String {
+	format_elapsed(Utc::now().naive_utc() - date)
+}
+
+/// 3 days => `3 days ago`
+fn format_elapsed(elapsed: chrono::Duration) -> String {
+	let (amount, unit) = match elapsed.num_seconds() {
+		seconds if seconds < 60 => return String::from("just now"),
+		seconds if seconds < 60 * 60 => (seconds / 60, "minute"),
+		seconds if seconds < 60 * 60 * 24 => (seconds / (60 * 60), "hour"),
+		seconds if seconds < 60 * 60 * 24 * 30 => (seconds / (60 * 60 * 24), "day"),
+		seconds if seconds < 60 * 60 * 24 * 365 => (seconds / (60 * 60 * 24 * 30), "month"),
+		seconds => (seconds / (60 * 60 * 24 * 365), "year"),
+	};
+
+	match amount {
+		1 => format!("1 {unit} ago"),
+		amount => format!("{amount} {unit}s ago"),
+	}
+}
+
+const fn tier_name(tier: Tier) -> &'static str {
+	match tier {
+		Tier::VeryEasy => "Very Easy",
+		Tier::Easy => "Easy",
+		Tier::Medium => "Medium",
+		Tier::Hard => "Hard",
+		Tier::VeryHard => "Very Hard",
+		Tier::Extreme => "Extreme",
+		Tier::Death => "Death",
+	}
+}
+
+const fn mode_long(mode: Mode) -> &'static str {
+	match mode {
+		Mode::KZTimer => "KZTimer",
+		Mode::SimpleKZ => "SimpleKZ",
+		Mode::Vanilla => "Vanilla",
+	}
+}
+
+const fn mode_api(mode: Mode) -> &'static str {
+	match mode {
+		Mode::KZTimer => "kz_timer",
+		Mode::SimpleKZ => "kz_simple",
+		Mode::Vanilla => "kz_vanilla",
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {super::*, chrono::NaiveDate};
+
+	fn record(time: f64) -> Option<Record> {
+		Some(Record {
+			time,
+			player_name: String::from("AlphaKeks"),
+			steam_id: "STEAM_1:1:161178172".parse().unwrap(),
+			created_on: NaiveDate::from_ymd_opt(2023, 4, 20)
+				.unwrap()
+				.and_hms_opt(0, 0, 0)
+				.unwrap(),
+		})
+	}
+
+	#[test]
+	fn render_templates() {
+		let state = State {
+			player_name: Some(String::from("AlphaKeks")),
+			steam_id: None,
+			map_name: Some(String::from("kz_lionharder")),
+			map_tier: Some(Tier::VeryHard),
+			mode: Some(Mode::SimpleKZ),
+		};
+
+		let records = Records { tp_wr: record(100.53), tp_pb: record(102.0), ..Default::default() };
+		let context = Context { state: &state, records: &records };
+
+		let cases = [
+			("{map}", "kz_lionharder"),
+			("Tier {tier|name}", "Tier Very Hard"),
+			// Escaped braces
+			("{{map}}", "{map}"),
+			("{{{map}}}", "{kz_lionharder}"),
+			("a } b", "a } b"),
+			// Unterminated placeholders
+			("{map", "{map"),
+			("{map} {", "kz_lionharder {"),
+			// Unknown variables and formatters
+			("{nope}", "{nope}"),
+			("{tp_wr_nope}", "{tp_wr_nope}"),
+			("{map|nope}", "{map|nope}"),
+			("{map|time}", "{map|time}"),
+			// Variables without a value
+			("{pro_wr_time}", ""),
+			("{pro_wr_time|seconds}", ""),
+			("{pro_wr_time|nope}", "{pro_wr_time|nope}"),
+			// Chained formatters
+			("{mode|short|lower}", "skz"),
+			("{ mode | api | upper }", "KZ_SIMPLE"),
+			("{tp_wr_player|upper|lower}", "alphakeks"),
+			// Times and dates
+			("{tp_wr_time}", "01:40.530"),
+			("{tp_wr_time|seconds}", "100.530"),
+			("{tp_pb_diff}", "00:01.470"),
+			("{tp_wr_date|date}", "2023/04/20"),
+		];
+
+		for (template, expected) in cases {
+			assert_eq!(render(template, context), expected, "{template}");
+		}
+	}
+
+	#[test]
+	fn format_times() {
+		let cases = [
+			(0.0, "00:00.000"),
+			(59.9994, "00:59.999"),
+			(59.9996, "01:00.000"),
+			(100.53, "01:40.530"),
+			(3599.9999, "01:00:00.000"),
+			(36061.5, "10:01:01.500"),
+			(-1.47, "-00:01.470"),
+		];
+
+		for (seconds, expected) in cases {
+			assert_eq!(format_time(seconds), expected, "{seconds}");
+		}
+	}
+
+	#[test]
+	fn format_relative_dates() {
+		const DAY: i64 = 60 * 60 * 24;
+
+		let cases = [
+			(-30, "just now"),
+			(59, "just now"),
+			(60, "1 minute ago"),
+			(119, "1 minute ago"),
+			(120, "2 minutes ago"),
+			(60 * 60 - 1, "59 minutes ago"),
+			(60 * 60, "1 hour ago"),
+			(DAY, "1 day ago"),
+			(DAY * 29, "29 days ago"),
+			(DAY * 30, "1 month ago"),
+			(DAY * 364, "12 months ago"),
+			(DAY * 365, "1 year ago"),
+			(DAY * 365 * 2, "2 years ago"),
+		];
+
+		for (seconds, expected) in cases {
+			assert_eq!(format_elapsed(chrono::Duration::seconds(seconds)), expected, "{seconds}");
+		}
+	}
+}
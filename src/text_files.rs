@@ -0,0 +1,203 @@
+//! Plain text files for OBS text sources set to "Read from file".
+//!
+//! Every entry of [`Config::text_files`] is rendered into [`Config::text_files_dir`] whenever the
+//! state or the records change.
+
+use {
+	crate::{
+		config::Config,
+		gsi::State,
+		records::Records,
+		template::{self, Context as TemplateContext},
+	},
+	color_eyre::{
+		eyre::{bail as yeet, Context},
+		Result,
+	},
+	std::{
+		collections::{BTreeMap, BTreeSet},
+		path::{Component, Path, PathBuf},
+		sync::Arc,
+		time::Duration,
+	},
+	tokio::sync::{
+		broadcast::{error::RecvError, Receiver},
+		Mutex,
+	},
+	tracing::{error, info, trace},
+};
+
+/// How often to check for config changes (e.g. a new folder) when nothing else happens.
+const CONFIG_INTERVAL: Duration = Duration::from_secs(2);
+
+pub async fn watch(
+	mut state_receiver: Receiver<State>,
+	mut records_receiver: Receiver<Records>,
+	config: Arc<Mutex<Config>>,
+) {
+	let mut state = State::default();
+	let mut records = Records::default();
+
+	// File path -> contents, so unchanged files aren't touched.
+	let mut written = BTreeMap::new();
+	// Files that couldn't be written last time, so a broken folder is only reported once.
+	let mut failing = BTreeSet::new();
+	let mut interval = tokio::time::interval(CONFIG_INTERVAL);
+
+	loop {
+		tokio::select! {
+			new_state = state_receiver.recv() => match new_state {
+				Ok(new_state) => state = new_state,
+				Err(RecvError::Lagged(_)) => continue,
+				Err(RecvError::Closed) => break,
+			},
+
+			new_records = records_receiver.recv() => match new_records {
+				Ok(new_records) => records = new_records,
+				Err(RecvError::Lagged(_)) => continue,
+				Err(RecvError::Closed) => break,
+			},
+
+			_ = interval.tick() => {}
+		}
+
+		let (dir, templates) = {
+			let config = config.lock().await;
+
+			let Some(dir) = config.text_files_dir.clone() else {
+				continue;
+			};
+
+			(dir, config.text_files.clone())
+		};
+
+		let context = TemplateContext {
+			state: &state,
+			records: &records,
+		};
+
+		for (file_name, template) in templates {
+			let text = template::render(&template, context);
+			let path = dir.join(&file_name);
+
+			if written.get(&path) == Some(&text) {
+				continue;
+			}
+
+			trace!("Writing `{}`.", path.display());
+
+			match write(&dir, &file_name, &text).await {
+				Ok(()) => {
+					if failing.remove(&path) {
+						info!("Writing `{}` works again.", path.display());
+					}
+
+					written.insert(path, text);
+				}
+				Err(why) => {
+					if failing.insert(path.clone()) {
+						error!("Failed to write `{}`: {why:?}", path.display());
+					}
+				}
+			}
+		}
+	}
+}
+
+/// Writes `text` to `<dir>/<file_name>`.
+///
+/// The text goes into a temporary file first which then replaces the actual file, so OBS never
+/// reads a half-written file.
+pub async fn write(dir: &Path, file_name: &str, text: &str) -> Result<()> {
+	let path = file_path(dir, file_name)?;
+
+	tokio::fs::create_dir_all(dir)
+		.await
+		.context("Failed to create text file folder.")?;
+
+	let temp_path = dir.join(format!(".{file_name}.tmp"));
+
+	tokio::fs::write(&temp_path, text)
+		.await
+		.context("Failed to write temporary file.")?;
+
+	tokio::fs::rename(&temp_path, &path)
+		.await
+		.context("Failed to replace file.")?;
+
+	Ok(())
+}
+
+/// Where `file_name` ends up inside of `dir`. Only plain file names are allowed, so a config
+/// entry can't write outside of the folder.
+fn file_path(dir: &Path, file_name: &str) -> Result<PathBuf> {
+	let mut components = Path::new(file_name).components();
+
+	if !matches!((components.next(), components.next()), (Some(Component::Normal(_)), None)) {
+		yeet!("`{file_name}` is not a valid file name.");
+	}
+
+	Ok(dir.join(file_name))
+}
+
+#[cfg(test)]
+mod tests {
+	use {super::*, std::fs, tempfile::TempDir};
+
+	#[test]
+	fn plain_file_names() {
+		let dir = Path::new("text_files");
+
+		assert_eq!(file_path(dir, "wr.txt").unwrap(), dir.join("wr.txt"));
+		assert_eq!(file_path(dir, ".hidden").unwrap(), dir.join(".hidden"));
+		assert_eq!(file_path(dir, "no extension").unwrap(), dir.join("no extension"));
+	}
+
+	#[test]
+	fn invalid_file_names() {
+		let dir = Path::new("text_files");
+
+		for file_name in ["", ".", "..", "../wr.txt", "nested/wr.txt", "/wr.txt"] {
+			assert!(file_path(dir, file_name).is_err(), "`{file_name}` should be rejected");
+		}
+	}
+
+	#[tokio::test]
+	async fn write_creates_folder() {
+		let temp_dir = TempDir::new().unwrap();
+		let dir = temp_dir.path().join("obs").join("text_files");
+
+		write(&dir, "map.txt", "kz_beginnerblock_go").await.unwrap();
+
+		assert_eq!(fs::read_to_string(dir.join("map.txt")).unwrap(), "kz_beginnerblock_go");
+	}
+
+	#[tokio::test]
+	async fn write_replaces_file() {
+		let temp_dir = TempDir::new().unwrap();
+		let dir = temp_dir.path();
+
+		write(dir, "wr.txt", "01:23.45").await.unwrap();
+		write(dir, "wr.txt", "01:02.03").await.unwrap();
+
+		assert_eq!(fs::read_to_string(dir.join("wr.txt")).unwrap(), "01:02.03");
+
+		// The temporary file is gone after the rename.
+		let file_names = fs::read_dir(dir)
+			.unwrap()
+			.map(|entry| entry.unwrap().file_name())
+			.collect::<Vec<_>>();
+
+		assert_eq!(file_names, ["wr.txt"]);
+	}
+
+	#[tokio::test]
+	async fn write_stays_in_folder() {
+		let temp_dir = TempDir::new().unwrap();
+		let dir = temp_dir.path().join("text_files");
+
+		assert!(write(&dir, "../escaped.txt", "nope").await.is_err());
+		assert!(!temp_dir.path().join("escaped.txt").exists());
+		assert!(!dir.exists());
+	}
+}
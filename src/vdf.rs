@@ -0,0 +1,181 @@
+//! Just enough of Valve's KeyValues format to read `libraryfolders.vdf` and GSI cfg files.
+
+/// A single `"key" "value"` pair.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pair {
+	/// The keys of the sections this pair is nested in, outermost first.
+	pub sections: Vec<String>,
+	pub key: String,
+	pub value: String,
+}
+
+/// Every key-value pair in `text`, in order. Malformed input is read as far as possible.
+pub fn parse(text: &str) -> Vec<Pair> {
+	let mut pairs = Vec::new();
+	let mut sections = Vec::new();
+	let mut key = None;
+
+	for token in tokenize(text) {
+		match (token, key.take()) {
+			(Token::Open, Some(section)) => sections.push(section),
+			// A section without a name; keep the nesting intact.
+			(Token::Open, None) => sections.push(String::new()),
+			(Token::Close, _) => {
+				sections.pop();
+			}
+			(Token::String(string), None) => key = Some(string),
+			(Token::String(value), Some(key)) => pairs.push(Pair {
+				sections: sections.clone(),
+				key,
+				value,
+			}),
+		}
+	}
+
+	pairs
+}
+
+#[derive(Debug)]
+enum Token {
+	String(String),
+	Open,
+	Close,
+}
+
+fn tokenize(text: &str) -> Vec<Token> {
+	let mut tokens = Vec::new();
+	let mut chars = text.chars().peekable();
+
+	while let Some(char) = chars.next() {
+		match char {
+			'{' => tokens.push(Token::Open),
+			'}' => tokens.push(Token::Close),
+			'"' => {
+				let mut string = String::new();
+
+				while let Some(char) = chars.next() {
+					match char {
+						'"' => break,
+						'\\' => string.extend(chars.next()),
+						char => string.push(char),
+					}
+				}
+
+				tokens.push(Token::String(string));
+			}
+			// Comments
+			'/' if chars.peek() == Some(&'/') => {
+				for char in chars.by_ref() {
+					if char == '\n' {
+						break;
+					}
+				}
+			}
+			char if char.is_whitespace() => {}
+			// Unquoted strings
+			char => {
+				let mut string = String::from(char);
+
+				while let Some(&char) = chars.peek() {
+					if char.is_whitespace() || matches!(char, '{' | '}' | '"') {
+						break;
+					}
+
+					string.push(char);
+					chars.next();
+				}
+
+				tokens.push(Token::String(string));
+			}
+		}
+	}
+
+	tokens
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn pair(sections: &[&str], key: &str, value: &str) -> Pair {
+		Pair {
+			sections: sections
+				.iter()
+				.map(|&section| String::from(section))
+				.collect(),
+			key: String::from(key),
+			value: String::from(value),
+		}
+	}
+
+	#[test]
+	fn sections() {
+		let text = r#"
+			"outer"
+			{
+				"key" "value"
+				"inner" { "nested" "1" }
+				"after" "2"
+			}
+		"#;
+
+		assert_eq!(
+			parse(text),
+			[
+				pair(&["outer"], "key", "value"),
+				pair(&["outer", "inner"], "nested", "1"),
+				pair(&["outer"], "after", "2"),
+			]
+		);
+	}
+
+	#[test]
+	fn escapes() {
+		let text = r#""path" "C:\\Program Files (x86)\\Steam" "quote" "say \"hi\"""#;
+
+		assert_eq!(
+			parse(text),
+			[
+				pair(&[], "path", r"C:\Program Files (x86)\Steam"),
+				pair(&[], "quote", r#"say "hi""#),
+			]
+		);
+	}
+
+	#[test]
+	fn comments() {
+		let text = "
+			// A comment
+			\"key\" \"value\" // Trailing comment
+			\"url\" \"http://127.0.0.1:8888\"
+		";
+
+		assert_eq!(
+			parse(text),
+			[
+				pair(&[], "key", "value"),
+				pair(&[], "url", "http://127.0.0.1:8888"),
+			]
+		);
+	}
+
+	#[test]
+	fn unquoted() {
+		assert_eq!(
+			parse("section { key value }"),
+			[pair(&["section"], "key", "value")]
+		);
+	}
+
+	#[test]
+	fn malformed() {
+		// Unnamed sections keep the nesting intact.
+		assert_eq!(
+			parse(r#"{ "a" "1" } "b" "2""#),
+			[pair(&[""], "a", "1"), pair(&[], "b", "2")]
+		);
+
+		// Unterminated strings end with the input.
+		assert_eq!(parse(r#""key" "val"#), [pair(&[], "key", "val")]);
+	}
+}